@@ -1,15 +1,17 @@
 use super::{
-    action::Action, player::Details, position::Position, scan::ScanResult, world_size::WorldSize,
+    action::Action, game::PlayerRng, player::Details, position::Position, scan::ScanResult,
+    world_size::WorldSize,
 };
 
 /// Represents the context that the game engine is sharing
 /// with the player logic in every interaction.
-#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug)]
 pub struct Context {
     health: u8,
     previous_action: Action,
     player_details: Details,
     position: Position,
+    rng: PlayerRng,
     scan: Option<ScanResult>,
     turn: usize,
     world_size: WorldSize,
@@ -17,11 +19,13 @@ pub struct Context {
 
 #[allow(dead_code)]
 impl Context {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         health: u8,
         previous_action: Action,
         player_details: Details,
         position: Position,
+        rng: PlayerRng,
         scan: Option<ScanResult>,
         turn: usize,
         world_size: WorldSize,
@@ -31,6 +35,7 @@ impl Context {
             player_details,
             position,
             previous_action,
+            rng,
             scan,
             turn,
             world_size,
@@ -49,6 +54,11 @@ impl Context {
         &self.position
     }
 
+    /// The random number generator of the player, seeded by the engine
+    pub fn rng(&self) -> PlayerRng {
+        self.rng.clone()
+    }
+
     pub fn scanned_data(&self) -> &Option<ScanResult> {
         &self.scan
     }
@@ -0,0 +1,104 @@
+//! Shapes drawn by the live viewer over the map, for showing the plans of a
+//! player (paths, targets, danger zones...) without printing anything
+
+use super::position::Position;
+
+/// Marked cells, lines and labels, in world coordinates. The live viewer draws
+/// the overlay of the inspected tank on demand.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DebugOverlay {
+    pub cells: Vec<Position>,
+    pub lines: Vec<(Position, Position)>,
+    pub labels: Vec<(Position, String)>,
+}
+
+impl DebugOverlay {
+    pub fn mark_cell(&mut self, position: Position) {
+        self.cells.push(position);
+    }
+
+    /// Marks all the cells of a path, e.g. as computed by the `PathFinder`
+    pub fn mark_path(&mut self, path: &[Position]) {
+        self.cells.extend_from_slice(path);
+    }
+
+    pub fn draw_line(&mut self, from: Position, to: Position) {
+        self.lines.push((from, to));
+    }
+
+    /// Writes a short text, starting at the given cell. Every cell holds two
+    /// characters of the label.
+    pub fn add_label(&mut self, at: Position, text: impl Into<String>) {
+        self.labels.push((at, text.into()));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty() && self.lines.is_empty() && self.labels.is_empty()
+    }
+
+    /// All the marked cells, including the ones crossed by the lines
+    pub fn covered_cells(&self) -> Vec<Position> {
+        let mut result = self.cells.clone();
+        for (from, to) in &self.lines {
+            result.extend(line_cells(from, to));
+        }
+        result
+    }
+}
+
+/// The cells crossed by a straight line, using Bresenham's algorithm
+fn line_cells(from: &Position, to: &Position) -> Vec<Position> {
+    let (mut x, mut y) = (from.x as isize, from.y as isize);
+    let (x1, y1) = (to.x as isize, to.y as isize);
+    let (dx, dy) = ((x1 - x).abs(), -(y1 - y).abs());
+    let (step_x, step_y) = ((x1 - x).signum(), (y1 - y).signum());
+    let mut error = dx + dy;
+
+    let mut result = Vec::new();
+    loop {
+        result.push(Position {
+            x: x as usize,
+            y: y as usize,
+        });
+        if x == x1 && y == y1 {
+            break;
+        }
+
+        let doubled = 2 * error;
+        if doubled >= dy {
+            error += dy;
+            x += step_x;
+        }
+        if doubled <= dx {
+            error += dx;
+            y += step_y;
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines_cover_the_crossed_cells() {
+        let mut overlay = DebugOverlay::default();
+        assert!(overlay.is_empty());
+
+        overlay.mark_cell(Position { x: 9, y: 9 });
+        overlay.draw_line(Position { x: 4, y: 1 }, Position { x: 0, y: 3 });
+        assert_eq!(
+            vec![
+                Position { x: 9, y: 9 },
+                Position { x: 4, y: 1 },
+                Position { x: 3, y: 2 },
+                Position { x: 2, y: 2 },
+                Position { x: 1, y: 3 },
+                Position { x: 0, y: 3 },
+            ],
+            overlay.covered_cells()
+        );
+    }
+}
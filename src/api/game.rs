@@ -0,0 +1,111 @@
+//! Information shared by the game engine with the players, when a game starts or ends
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+
+use super::{
+    log::Logger,
+    player::{PlayerId, PlayerIdentity},
+    storage::Storage,
+    world_size::WorldSize,
+};
+
+/// The rules under which a game is played.
+///
+/// Damage values are expressed as a percentage of the full health.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GameRules {
+    pub max_turns: usize,
+    pub damage_direct_hit: u16,
+    pub damage_indirect_hit: u16,
+    pub damage_collision_with_player: u16,
+    pub damage_collision_with_forest: u16,
+    pub damage_sinking_into_lake: u16,
+    pub score_direct_hit: u16,
+    pub score_indirect_hit: u16,
+    pub score_killing: u16,
+    pub score_survivor: u16,
+}
+
+/// A random number generator private to a single player.
+///
+/// The engine seeds it from the game seed and the identity of the player, so
+/// players drawing their random numbers from it behave the same way whenever
+/// a game is replayed with the same seed. It is provided in every `Context`,
+/// and when the game starts. The handle can be cloned and kept around, all the
+/// clones drawing from the same sequence.
+#[derive(Clone, Debug)]
+pub struct PlayerRng {
+    rng: Arc<Mutex<StdRng>>,
+}
+
+impl PlayerRng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self {
+            rng: Arc::new(Mutex::new(StdRng::seed_from_u64(seed))),
+        }
+    }
+}
+
+impl RngCore for PlayerRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.lock().unwrap().next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.lock().unwrap().next_u64()
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        self.rng.lock().unwrap().fill_bytes(dst)
+    }
+}
+
+/// Provided to the player right before the first turn of a game.
+#[derive(Clone, Debug)]
+pub struct GameStart {
+    pub game_id: u32,
+    pub identity: PlayerIdentity,
+    pub logger: Logger,
+    pub roster: Vec<PlayerIdentity>,
+    pub rules: GameRules,
+    pub rng: PlayerRng,
+    pub seed: u64,
+    pub storage: Storage,
+    pub world_size: WorldSize,
+}
+
+/// Describes what killed a player
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DeathCause {
+    DirectHit(PlayerId),
+    IndirectHit(PlayerId),
+    CollisionWithPlayer(PlayerId),
+    CollisionWithForest,
+    Drowning,
+}
+
+impl std::fmt::Display for DeathCause {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            Self::DirectHit(id) => format!("DirectHit(by {id})"),
+            Self::IndirectHit(id) => format!("IndirectHit(by {id})"),
+            Self::CollisionWithPlayer(id) => format!("CollisionWithPlayer({id})"),
+            Self::CollisionWithForest => "CollisionWithForest".to_string(),
+            Self::Drowning => "Drowning".to_string(),
+        };
+        write!(f, "{text}")
+    }
+}
+
+/// Provided to every player of a game, after the game is over
+#[derive(Clone, Debug)]
+pub struct GameEnd {
+    pub game_id: u32,
+    pub ranks: HashMap<PlayerId, u8>,
+    pub scores: HashMap<PlayerId, u16>,
+}
@@ -0,0 +1,150 @@
+//! Per-player log, which players can use instead of printing to the standard
+//! output, since printing corrupts the live view
+
+use std::{
+    collections::VecDeque,
+    fs,
+    io::{self, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+/// Specifies the maximum amount of entries kept in a log, the oldest ones being dropped
+pub const MAX_LOG_ENTRIES: usize = 10_000;
+
+/// A line of a log, along with the turn during which it was written
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LogEntry {
+    pub turn: usize,
+    pub text: String,
+}
+
+#[derive(Debug, Default)]
+struct Buffer {
+    dropped: usize,
+    entries: VecDeque<LogEntry>,
+    turn: usize,
+}
+
+/// A log private to a single player.
+///
+/// The engine provides it to the player when a game starts, and tags every
+/// entry with the current turn. The log of the inspected tank is shown in the
+/// live view, and the logs of all the players can be written to files after
+/// every game. The handle can be cloned and kept around.
+#[derive(Clone, Debug, Default)]
+pub struct Logger {
+    buffer: Arc<Mutex<Buffer>>,
+}
+
+impl Logger {
+    /// Appends a line to the log
+    pub fn log(&self, text: impl Into<String>) {
+        let mut buffer = self.buffer.lock().unwrap();
+
+        if buffer.entries.len() == MAX_LOG_ENTRIES {
+            buffer.entries.pop_front();
+            buffer.dropped += 1;
+        }
+        let turn = buffer.turn;
+        buffer.entries.push_back(LogEntry {
+            turn,
+            text: text.into(),
+        });
+    }
+
+    /// Selects the turn with which the next entries are tagged
+    pub(crate) fn set_turn(&self, turn: usize) {
+        self.buffer.lock().unwrap().turn = turn;
+    }
+
+    /// Returns up to `count` of the last entries written until the given turn
+    pub(crate) fn last_entries(&self, count: usize, until_turn: usize) -> Vec<LogEntry> {
+        let buffer = self.buffer.lock().unwrap();
+        let mut result = buffer
+            .entries
+            .iter()
+            .rev()
+            .filter(|entry| entry.turn <= until_turn)
+            .take(count)
+            .cloned()
+            .collect::<Vec<_>>();
+        result.reverse();
+        result
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.buffer.lock().unwrap().entries.is_empty()
+    }
+
+    /// Writes the whole log into the file at `path`, one entry per line
+    pub(crate) fn write_to(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let buffer = self.buffer.lock().unwrap();
+        let mut file = io::BufWriter::new(fs::File::create(path)?);
+        if buffer.dropped > 0 {
+            writeln!(file, "({} older entries dropped)", buffer.dropped)?;
+        }
+        for entry in &buffer.entries {
+            writeln!(file, "turn {:04}\t{}", entry.turn, entry.text)?;
+        }
+
+        file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entries_are_tagged_with_the_turn() {
+        let logger = Logger::default();
+        let handle = logger.clone();
+        assert!(logger.is_empty());
+
+        handle.log("starting");
+        logger.set_turn(3);
+        handle.log("target at [x:004, y:002]");
+        handle.log(format!("health {}", 75));
+
+        assert_eq!(
+            vec![
+                LogEntry {
+                    turn: 3,
+                    text: "target at [x:004, y:002]".to_string()
+                },
+                LogEntry {
+                    turn: 3,
+                    text: "health 75".to_string()
+                },
+            ],
+            logger.last_entries(2, 3)
+        );
+        assert_eq!(1, logger.last_entries(5, 2).len());
+
+        let path = std::env::temp_dir().join(format!("rbt-log-{}.log", std::process::id()));
+        logger.write_to(&path).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(
+            "turn 0000\tstarting\nturn 0003\ttarget at [x:004, y:002]\nturn 0003\thealth 75\n",
+            content
+        );
+    }
+
+    #[test]
+    fn test_oldest_entries_are_dropped() {
+        let logger = Logger::default();
+        for i in 0..MAX_LOG_ENTRIES + 2 {
+            logger.log(i.to_string());
+        }
+
+        let entries = logger.last_entries(MAX_LOG_ENTRIES, 0);
+        assert_eq!(MAX_LOG_ENTRIES, entries.len());
+        assert_eq!("2", entries[0].text);
+    }
+}
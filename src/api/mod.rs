@@ -3,7 +3,10 @@
 pub mod action;
 pub mod aiming;
 pub mod context;
+pub mod debug_overlay;
 pub mod direction;
+pub mod game;
+pub mod log;
 pub mod map_cell;
 pub mod orientation;
 pub mod path_finder;
@@ -11,4 +14,5 @@ pub mod player;
 pub mod position;
 pub mod rotation;
 pub mod scan;
+pub mod storage;
 pub mod world_size;
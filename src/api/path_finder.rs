@@ -18,6 +18,20 @@ pub trait MapReader: Clone {
     fn read_at(&self, position: &Position) -> MapCell;
 }
 
+/// The object-safe counterpart of `MapReader`, through which the live viewer
+/// shows the world as known by a player. Every `MapReader` implements it.
+pub trait BeliefMap {
+    /// Returns the MapCell found at `position`, as known by the player.
+    /// Unknown cells are `MapCell::Unallocated`.
+    fn believed_cell(&self, position: &Position) -> MapCell;
+}
+
+impl<M: MapReader> BeliefMap for M {
+    fn believed_cell(&self, position: &Position) -> MapCell {
+        self.read_at(position)
+    }
+}
+
 #[allow(dead_code)]
 struct PathDetails {
     from: Position,
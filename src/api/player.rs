@@ -1,10 +1,20 @@
 //! The Player trait must be implemented by every player
 
-use super::{action::Action, context::Context, orientation::Orientation};
+use super::{
+    action::Action,
+    context::Context,
+    debug_overlay::DebugOverlay,
+    game::{DeathCause, GameEnd, GameStart},
+    orientation::Orientation,
+    path_finder::BeliefMap,
+};
 
 pub type Avatar = char;
 pub type PlayerId = u8;
 
+/// The avatar used for representing dead players
+pub const DEAD_AVATAR: Avatar = '💀';
+
 /// An invalid player details instance
 pub const INVALID_PLAYER: Details = Details {
     avatar: ' ',
@@ -17,13 +27,22 @@ pub const INVALID_PLAYER: Details = Details {
 /// to be able to interact with the player.
 pub trait Player: Send {
     /// Implement this method if and only if you need to perform expensive and
-    /// potentially failing initialization.
+    /// potentially failing initialization. It is invoked before every game.
     ///
     /// The return value should indicate the initialization success.
     fn initialized(&mut self) -> bool {
         true
     }
 
+    /// Invoked once the game is set up, right before its first turn.
+    fn on_game_start(&mut self, _game: GameStart) {}
+
+    /// Invoked at the end of the turn in which the player died.
+    fn on_death(&mut self, _cause: DeathCause) {}
+
+    /// Invoked once the game is over, with the final ranks and scores.
+    fn on_game_end(&mut self, _result: GameEnd) {}
+
     /// This is the player's turn to fight.
     ///
     /// The changes performed by the game engine are provided in the `context`.
@@ -32,10 +51,51 @@ pub trait Player: Send {
     /// Returns the player's name
     fn name(&self) -> String;
 
+    /// Shapes drawn over the map by the live viewer, when inspecting the tank
+    /// of the player. This is the place for showing paths, targets or danger
+    /// zones, instead of printing them.
+    fn debug_overlay(&self) -> DebugOverlay {
+        DebugOverlay::default()
+    }
+
+    /// The model of the world kept by the player, if any, e.g. the `MapReader`
+    /// provided to the `PathFinder`. The live viewer can show it in place of
+    /// the true map, which reveals whether it drifts from reality.
+    fn belief_map(&self) -> Option<&dyn BeliefMap> {
+        None
+    }
+
     /// This indicates whether the player is ready for battle or not.
     fn is_ready(&self) -> bool {
         false
     }
+
+    /// Whether the same player instance takes part in all the games of a
+    /// championship, keeping its state from one game to the next one. By
+    /// default, every game is played by a fresh instance, created by the
+    /// player factory. Headless games always use fresh instances.
+    fn keeps_state(&self) -> bool {
+        false
+    }
+}
+
+/// The identity of a player, allocated once by the championship and kept
+/// unchanged in every game the player takes part in.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PlayerIdentity {
+    pub id: PlayerId,
+    pub name: String,
+    pub avatar: Avatar,
+}
+
+impl std::fmt::Display for PlayerIdentity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{id: {}, name: {}, avatar: {}}}",
+            self.id, self.name, self.avatar
+        )
+    }
 }
 
 /// Defines the player id type
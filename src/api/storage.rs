@@ -0,0 +1,311 @@
+//! Persistent storage, which players can use for keeping data between games and championships
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, OnceLock, PoisonError},
+};
+
+/// Specifies the maximum amount of bytes that a player may keep in its storage
+pub const STORAGE_CAPACITY: usize = 1024 * 1024;
+
+/// Specifies the maximum length of a storage key
+pub const MAX_KEY_LENGTH: usize = 64;
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StorageError {
+    /// Keys must be non-empty, at most `MAX_KEY_LENGTH` long, and may contain
+    /// only ASCII letters, digits, `-` and `_`
+    InvalidKey,
+    /// Storing the value would exceed the `STORAGE_CAPACITY`
+    CapacityExceeded,
+    /// The storage is not backed by any directory
+    Unavailable,
+    /// The underlying file system operation failed
+    Io(String),
+}
+
+impl std::fmt::Display for StorageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidKey => write!(f, "invalid key"),
+            Self::CapacityExceeded => write!(f, "capacity of {STORAGE_CAPACITY} bytes exceeded"),
+            Self::Unavailable => write!(f, "storage unavailable"),
+            Self::Io(e) => write!(f, "I/O error: {e}"),
+        }
+    }
+}
+
+/// A sandboxed key/value store, private to a single player.
+///
+/// The engine provides it to the player when a game starts. Every value is
+/// persisted as soon as it is set, so it survives the end of the game and even
+/// the end of the championship. The handle can be cloned and kept around.
+///
+/// The concurrent headless games share the storage of their players, so every
+/// operation is performed under a lock of the storage, and every value is
+/// replaced at once, never leaving it half-written.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Storage {
+    directory: Option<PathBuf>,
+}
+
+impl Storage {
+    /// Opens the storage of the player called `player_name`, located under `root`.
+    ///
+    /// Every byte of the name other than an ASCII letter or digit is escaped as
+    /// `%XX`, so that distinct names never share a directory. A player with an
+    /// empty name gets an unavailable storage.
+    pub(crate) fn open(root: &Path, player_name: &str) -> Self {
+        if player_name.is_empty() {
+            return Self::default();
+        }
+
+        let name = player_name
+            .bytes()
+            .map(|b| {
+                if b.is_ascii_alphanumeric() {
+                    (b as char).to_string()
+                } else {
+                    format!("%{b:02X}")
+                }
+            })
+            .collect::<String>();
+
+        Self {
+            directory: Some(root.join(name)),
+        }
+    }
+
+    /// Returns the value stored under `key`, if any
+    pub fn get(&self, key: &str) -> Option<Vec<u8>> {
+        let path = self.path(key).ok()?;
+        self.locked(|| fs::read(path).ok())
+    }
+
+    /// Returns the value stored under `key`, if any and if it is valid UTF-8
+    pub fn get_string(&self, key: &str) -> Option<String> {
+        self.get(key)
+            .and_then(|value| String::from_utf8(value).ok())
+    }
+
+    /// Stores `value` under `key`, replacing any previous value
+    pub fn set(&self, key: &str, value: &[u8]) -> Result<(), StorageError> {
+        let path = self.path(key)?;
+
+        self.locked(|| {
+            let previous = fs::metadata(&path).map(|m| m.len() as usize).unwrap_or(0);
+            let used = self.entries().iter().map(|(_, size)| size).sum::<usize>();
+
+            if used.saturating_sub(previous) + value.len() > STORAGE_CAPACITY {
+                return Err(StorageError::CapacityExceeded);
+            }
+
+            // the value is written aside, and then moved over the previous one
+            let temporary = path.with_file_name(format!(".{key}.tmp"));
+            if let Some(directory) = &self.directory {
+                fs::create_dir_all(directory).map_err(|e| StorageError::Io(e.to_string()))?;
+            }
+            fs::write(&temporary, value).map_err(|e| StorageError::Io(e.to_string()))?;
+            fs::rename(temporary, path).map_err(|e| StorageError::Io(e.to_string()))
+        })
+    }
+
+    /// Removes the value stored under `key`, if any
+    pub fn remove(&self, key: &str) -> Result<(), StorageError> {
+        let path = self.path(key)?;
+
+        self.locked(|| {
+            if path.exists() {
+                fs::remove_file(path).map_err(|e| StorageError::Io(e.to_string()))?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Lists all the keys which have a value stored
+    pub fn keys(&self) -> Vec<String> {
+        let mut keys = self
+            .locked(|| self.entries())
+            .into_iter()
+            .map(|(key, _)| key)
+            .collect::<Vec<_>>();
+        keys.sort();
+
+        keys
+    }
+
+    /// Returns the amount of bytes currently stored
+    pub fn used(&self) -> usize {
+        self.locked(|| self.entries())
+            .iter()
+            .map(|(_, size)| size)
+            .sum()
+    }
+}
+
+// Private functions
+impl Storage {
+    /// Runs `operation` while holding the lock of the storage, which is shared
+    /// by all the handles to the same directory
+    fn locked<T>(&self, operation: impl FnOnce() -> T) -> T {
+        static LOCKS: OnceLock<Mutex<HashMap<PathBuf, Arc<Mutex<()>>>>> = OnceLock::new();
+
+        let Some(directory) = &self.directory else {
+            return operation();
+        };
+
+        let lock = LOCKS
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(directory.clone())
+            .or_default()
+            .clone();
+        let _guard = lock.lock().unwrap_or_else(PoisonError::into_inner);
+
+        operation()
+    }
+
+    /// Lists the stored values along with their size, leaving out any value
+    /// still being written
+    fn entries(&self) -> Vec<(String, usize)> {
+        let mut result = Vec::new();
+
+        if let Some(Ok(entries)) = self.directory.as_ref().map(fs::read_dir) {
+            for entry in entries.flatten() {
+                if let (Ok(name), Ok(metadata)) =
+                    (entry.file_name().into_string(), entry.metadata())
+                    && metadata.is_file()
+                    && Self::valid_key(&name)
+                {
+                    result.push((name, metadata.len() as usize));
+                }
+            }
+        }
+
+        result
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf, StorageError> {
+        if !Self::valid_key(key) {
+            return Err(StorageError::InvalidKey);
+        }
+
+        match &self.directory {
+            Some(directory) => Ok(directory.join(key)),
+            None => Err(StorageError::Unavailable),
+        }
+    }
+
+    fn valid_key(key: &str) -> bool {
+        !key.is_empty()
+            && key.len() <= MAX_KEY_LENGTH
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("rbt-storage-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        root
+    }
+
+    #[test]
+    fn test_values_persist_between_handles() {
+        let root = temp_root("persist");
+
+        let storage = Storage::open(&root, "Shin-chan");
+        assert_eq!(None, storage.get("wins"));
+        assert!(storage.set("wins", b"3").is_ok());
+
+        let reopened = Storage::open(&root, "Shin-chan");
+        assert_eq!(Some("3".to_string()), reopened.get_string("wins"));
+        assert_eq!(vec!["wins".to_string()], reopened.keys());
+        assert!(reopened.remove("wins").is_ok());
+        assert!(storage.keys().is_empty());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_storage_is_sandboxed() {
+        let root = temp_root("sandbox");
+        let storage = Storage::open(&root, "../escape");
+
+        assert_eq!(Err(StorageError::InvalidKey), storage.set("../other", b"x"));
+        assert_eq!(Err(StorageError::InvalidKey), storage.set("", b"x"));
+        assert!(storage.set("key", b"x").is_ok());
+        assert!(root.join("%2E%2E%2Fescape").join("key").exists());
+
+        assert_eq!(
+            Err(StorageError::Unavailable),
+            Storage::default().set("key", b"x")
+        );
+        assert_eq!(
+            Err(StorageError::Unavailable),
+            Storage::open(&root, "").set("key", b"x")
+        );
+
+        // names differing only in their special characters do not share a directory
+        assert!(Storage::open(&root, "Shin-chan").set("key", b"x").is_ok());
+        assert_eq!(None, Storage::open(&root, "Shin chan").get("key"));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_concurrent_handles_share_the_capacity() {
+        let root = temp_root("concurrent");
+        let value = vec![0u8; STORAGE_CAPACITY / 8];
+
+        std::thread::scope(|scope| {
+            for thread in 0..4 {
+                let (root, value) = (&root, &value);
+                scope.spawn(move || {
+                    let storage = Storage::open(root, "player");
+                    for round in 0..10 {
+                        let key = format!("key{}", (thread + round) % 12);
+                        let _ = storage.set(&key, value);
+                        let _ = storage.remove(&format!("key{}", round % 12));
+                    }
+                });
+            }
+        });
+
+        let storage = Storage::open(&root, "player");
+        assert!(storage.used() <= STORAGE_CAPACITY);
+        assert!(
+            storage
+                .keys()
+                .iter()
+                .all(|key| storage.get(key) == Some(value.clone()))
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_capacity_is_enforced() {
+        let root = temp_root("capacity");
+        let storage = Storage::open(&root, "player");
+
+        let half = vec![0u8; STORAGE_CAPACITY / 2];
+        assert!(storage.set("a", &half).is_ok());
+        assert!(storage.set("b", &half).is_ok());
+        assert_eq!(Err(StorageError::CapacityExceeded), storage.set("c", b"x"));
+
+        // replacing a value only accounts for the difference
+        assert!(storage.set("b", b"x").is_ok());
+        assert!(storage.set("c", b"x").is_ok());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,111 @@
+//! Recording of the terminal output as an asciinema v2 cast, which replays a
+//! session exactly as it looked with the standard asciinema tools
+
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+// Used when the size of the terminal cannot be queried
+const DEFAULT_WIDTH: u16 = 80;
+const DEFAULT_HEIGHT: u16 = 24;
+
+pub struct Cast {
+    file: BufWriter<File>,
+    pending: Vec<u8>,
+    start: Instant,
+}
+
+impl Cast {
+    /// Creates the cast file, and writes its header
+    pub fn create(path: &Path) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let (width, height) = match crossterm::terminal::size() {
+            Ok((width, height)) if width > 0 && height > 0 => (width, height),
+            _ => (DEFAULT_WIDTH, DEFAULT_HEIGHT),
+        };
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        let mut file = BufWriter::new(File::create(path)?);
+        writeln!(
+            file,
+            "{{\"version\": 2, \"width\": {width}, \"height\": {height}, \"timestamp\": {timestamp}}}"
+        )?;
+
+        Ok(Self {
+            file,
+            pending: Vec::new(),
+            start: Instant::now(),
+        })
+    }
+
+    /// Collects the output of the current frame
+    pub fn record(&mut self, output: &[u8]) {
+        self.pending.extend_from_slice(output);
+    }
+
+    /// Writes the collected output as a single event, stamped with the time
+    /// elapsed since the recording started
+    pub fn flush_frame(&mut self) -> io::Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+
+        let elapsed = self.start.elapsed().as_secs_f64();
+        let data = escape(&String::from_utf8_lossy(&self.pending));
+        self.pending.clear();
+
+        writeln!(self.file, "[{elapsed:.6}, \"o\", \"{data}\"]")?;
+        self.file.flush()
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => result += "\\\"",
+            '\\' => result += "\\\\",
+            '\n' => result += "\\n",
+            '\r' => result += "\\r",
+            c if (c as u32) < 0x20 || c == '\u{7f}' => result += &format!("\\u{:04x}", c as u32),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frames_are_recorded_as_events() {
+        let path = std::env::temp_dir().join(format!("rbt-cast-{}.cast", std::process::id()));
+
+        let mut cast = Cast::create(&path).unwrap();
+        cast.record(b"\x1b[1;1H");
+        cast.record("🟩 \"tank\"\n".as_bytes());
+        cast.flush_frame().unwrap();
+        // nothing was collected since the last frame
+        cast.flush_frame().unwrap();
+        drop(cast);
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let lines = content.lines().collect::<Vec<_>>();
+
+        assert_eq!(2, lines.len());
+        assert!(lines[0].starts_with("{\"version\": 2, "));
+        assert!(lines[1].starts_with('['));
+        assert!(lines[1].ends_with(r#", "o", "\u001b[1;1H🟩 \"tank\"\n"]"#));
+    }
+}
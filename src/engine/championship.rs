@@ -1,79 +1,649 @@
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap, HashSet},
+    fs,
+    path::PathBuf,
+};
+
+use rand::{rngs::StdRng, seq::IndexedRandom, SeedableRng};
+use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
+
 use crate::{
-    api::{player::Player, world_size::WorldSize},
+    api::{
+        player::{Player, PlayerId, PlayerIdentity},
+        world_size::WorldSize,
+    },
     engine::{
-        game::Game,
+        game::{avatar, Game},
+        human::HumanPlayer,
         outcome::{ChampionshipOutcome, GameOutcome},
+        rating::{RatingChange, Ratings},
+        seed::game_seed,
+        theme::Theme,
+        tournament::{
+            elimination_pairs, round_robin_pairs, standings, swiss_pairs, Format, MatchResult,
+            Standing,
+        },
     },
-    players::{
-        alvarez::Luis, armholt::Swede, arola::Arola, fox::TwentyCenturyFox, karjalainen::Miklas,
-        laurikainen::PlayerOne, moykkynen::Joonas, niemisto::Niemisto, pop::Aurelian, rahtu::Rahtu,
-        salonen::Es, siimesjarvi::Siimesjarvi, terava::PlAgiAntti,
-    },
+    terminal::get_terminal,
 };
 
+/// The directory holding the players' persistent storage, unless another one is set
+pub const DEFAULT_STORAGE_DIRECTORY: &str = ".rbt/storage";
+
+/// Creates a new instance of a player
+pub type PlayerFactory = fn() -> Box<dyn Player>;
+
 pub struct Championship {
+    cast_file: Option<PathBuf>,
+    format: Format,
+    headless: bool,
+    human_player: bool,
+    log_directory: Option<PathBuf>,
+    players: Vec<PlayerFactory>,
+    ratings_file: Option<PathBuf>,
+    seed: u64,
+    storage_directory: PathBuf,
+    theme: Theme,
+    wipe_storage: bool,
     world_size: WorldSize,
 }
 
 impl Championship {
-    pub fn new(world_size: WorldSize) -> Self {
-        Championship { world_size }
+    pub fn new(world_size: WorldSize, players: Vec<PlayerFactory>) -> Self {
+        Championship {
+            cast_file: None,
+            format: Format::default(),
+            headless: false,
+            human_player: false,
+            log_directory: None,
+            players,
+            ratings_file: None,
+            seed: rand::random(),
+            storage_directory: PathBuf::from(DEFAULT_STORAGE_DIRECTORY),
+            theme: Theme::default(),
+            wipe_storage: false,
+            world_size,
+        }
+    }
+
+    /// Selects the directory holding the players' persistent storage. When `wipe`
+    /// is set, all the stored data is deleted before the championship starts,
+    /// which is useful for clean benchmarking runs.
+    pub fn set_storage(&mut self, directory: impl Into<PathBuf>, wipe: bool) {
+        self.storage_directory = directory.into();
+        self.wipe_storage = wipe;
+    }
+
+    /// Selects the file where the ratings of the players are kept between
+    /// championships. Without it, every championship starts from the initial
+    /// ratings.
+    pub fn set_ratings_file(&mut self, path: impl Into<PathBuf>) {
+        self.ratings_file = Some(path.into());
+    }
+
+    /// Selects the seed from which the seeds of all the games are derived. By
+    /// default, a random seed is used.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Selects whether the games are played without any rendering nor delays.
+    /// Headless games are played concurrently, using a fresh instance of every
+    /// player for every game, even for the players keeping their state.
+    pub fn set_headless(&mut self, headless: bool) {
+        self.headless = headless;
+    }
+
+    /// Selects whether a player controlled from the keyboard takes part in the
+    /// championship, after the other players. Headless championships are
+    /// played by the other players only.
+    pub fn set_human_player(&mut self, human_player: bool) {
+        self.human_player = human_player;
+    }
+
+    /// Selects an asciinema cast file, where every frame of the games is
+    /// recorded along with its timestamp. Headless games are not recorded,
+    /// since they are not rendered.
+    pub fn set_cast_file(&mut self, path: impl Into<PathBuf>) {
+        self.cast_file = Some(path.into());
+    }
+
+    /// Selects a directory where the logs of the players are written after
+    /// every game, under a `game_<id>` subdirectory
+    pub fn set_log_directory(&mut self, directory: impl Into<PathBuf>) {
+        self.log_directory = Some(directory.into());
+    }
+
+    /// Selects how the map is rendered in the live viewer. The ASCII theme
+    /// suits the terminals which do not render the emoji with a consistent width.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    /// Selects how the players meet in the games. By default, all the players
+    /// take part in every game.
+    ///
+    /// Panics for random subsets of less than 2 players, or of more players than
+    /// the championship has. When some players are not ready, the subsets are
+    /// limited to the ready ones.
+    pub fn set_format(&mut self, format: Format) {
+        if let Format::RandomSubsets { size } = format
+            && !(2..=self.players.len()).contains(&size)
+        {
+            panic!(
+                "Invalid random subsets of {size} players, out of {} players",
+                self.players.len()
+            );
+        }
+
+        self.format = format;
     }
 
+    /// Runs the championship. For the free-for-all formats, `rounds` is the
+    /// amount of games, while for the duel formats it is the amount of games
+    /// played in every duel.
     pub fn run(&mut self, rounds: u32) -> ChampionshipOutcome {
-        let mut championship_outcome = ChampionshipOutcome::new();
-        let players = self.get_players();
-
-        let mut player_id = 0;
-        for player in players {
-            if player.is_ready() {
-                player_id += 1;
-                championship_outcome.register_player(player_id, player.name());
+        if self.wipe_storage
+            && self.storage_directory.exists()
+            && let Err(e) = fs::remove_dir_all(&self.storage_directory)
+        {
+            panic!(
+                "Unable to wipe the storage at {}: {e}",
+                self.storage_directory.display()
+            );
+        }
+
+        let ratings = match &self.ratings_file {
+            Some(path) => Ratings::load(path).unwrap_or_else(|e| {
+                panic!("Unable to load the ratings from {}: {e}", path.display())
+            }),
+            None => Ratings::default(),
+        };
+
+        let recording = match &self.cast_file {
+            Some(path) if !self.headless => {
+                if let Err(e) = get_terminal().lock().unwrap().start_recording(path) {
+                    panic!("Unable to record the games to {}: {e}", path.display());
+                }
+                true
             }
+            _ => false,
+        };
+
+        let (mut roster, mut entrants) = self.create_roster();
+        let human = (self.human_player && !self.headless)
+            .then(|| Self::enter_human_player(&mut roster, &mut entrants));
+        let mut session = Session {
+            entrants,
+            game_id: 0,
+            human,
+            outcome: ChampionshipOutcome::new(),
+            quit: false,
+            ratings,
+            roster,
+        };
+
+        session.outcome.set_setup(self.seed, self.format.clone());
+        for (identity, _) in &session.roster {
+            session.outcome.register_player(identity.clone());
         }
+        let initial_ratings = session
+            .roster
+            .iter()
+            .map(|(identity, _)| (identity.id, session.ratings.rating(&identity.name)))
+            .collect::<Vec<_>>();
+        let players = initial_ratings
+            .iter()
+            .map(|(player_id, _)| *player_id)
+            .collect::<Vec<_>>();
 
-        for i in 0..rounds {
-            let game_id = i + 1;
-            let (quit, game_outcome) = self.run_single_game(game_id, self.world_size.clone());
+        match self.format.clone() {
+            Format::FreeForAll => {
+                let games = vec![players.clone(); rounds as usize];
+                self.play_games(&mut session, &games);
+            }
+            Format::RandomSubsets { size } => {
+                let mut rng = StdRng::seed_from_u64(self.seed);
+                let games = (0..rounds)
+                    .map(|_| {
+                        let mut subset = players
+                            .choose_multiple(&mut rng, size)
+                            .copied()
+                            .collect::<Vec<_>>();
+                        subset.sort();
+                        subset
+                    })
+                    .collect::<Vec<_>>();
+                self.play_games(&mut session, &games);
+            }
+            Format::RoundRobin => {
+                let mut points = players.iter().map(|id| (*id, 0.0)).collect();
+                let pairs = round_robin_pairs(&players);
+                for result in self.play_matches(&mut session, &pairs, rounds, false) {
+                    Self::add_match_points(&mut points, &result);
+                }
+                session
+                    .outcome
+                    .set_standings(standings(&Vec::from_iter(points), false));
+            }
+            Format::Swiss {
+                rounds: swiss_rounds,
+            } => self.run_swiss(&mut session, &players, swiss_rounds, rounds),
+            Format::SingleElimination => self.run_elimination(&mut session, &players, 1, rounds),
+            Format::DoubleElimination => self.run_elimination(&mut session, &players, 2, rounds),
+        }
 
-            championship_outcome.add_game_result(game_outcome);
-            println!("Game {game_id} finished");
+        if recording {
+            get_terminal().lock().unwrap().stop_recording();
+        }
 
-            if quit {
-                break;
+        session.outcome.compute_ranks();
+        if !self.format.duels() {
+            let points = session.outcome.get_ranks().into_iter().collect::<Vec<_>>();
+            session.outcome.set_standings(standings(&points, true));
+        }
+
+        for (player_id, before) in initial_ratings {
+            if let Some(name) = session.outcome.get_player_name(player_id) {
+                let after = session.ratings.rating(&name);
+                session
+                    .outcome
+                    .add_rating_change(player_id, RatingChange { before, after });
             }
         }
-        championship_outcome.compute_ranks();
+        if let Some(path) = &self.ratings_file
+            && let Err(e) = session.ratings.save(path)
+        {
+            panic!("Unable to save the ratings to {}: {e}", path.display());
+        }
 
-        championship_outcome
+        session.outcome
     }
 }
 
+/// The state of a running championship
+struct Session {
+    /// The factories of the players, used for creating a fresh instance of
+    /// every player for every game, unless the player keeps its state
+    entrants: Vec<(PlayerIdentity, PlayerFactory)>,
+    game_id: u32,
+    /// The player controlled from the keyboard, if any, along with the handle
+    /// through which the keys are handed over to it
+    human: Option<(PlayerId, HumanPlayer)>,
+    outcome: ChampionshipOutcome,
+    quit: bool,
+    ratings: Ratings,
+    roster: Roster,
+}
+
+/// The players taking part in the championship, along with their identities
+type Roster = Vec<(PlayerIdentity, Box<dyn Player>)>;
+
+/// The ranks and the scores of a game
+type GameResult = (HashMap<PlayerId, u8>, HashMap<PlayerId, u16>);
+
 impl Championship {
-    fn run_single_game(&self, game_id: u32, world_size: WorldSize) -> (bool, GameOutcome) {
-        let mut game = Game::new(world_size);
-        game.spawn_players(self.get_players());
-
-        game.start(game_id)
-    }
-
-    fn get_players(&self) -> Vec<Box<dyn Player>> {
-        let result: Vec<Box<dyn Player>> = vec![
-            Box::new(Luis::new()),
-            Box::new(Swede::new()),
-            Box::new(Arola::new()),
-            Box::new(PlayerOne::new()),
-            Box::new(Joonas::new()),
-            Box::new(Niemisto::new()),
-            Box::new(Rahtu::new()),
-            Box::new(Es::new()),
-            Box::new(Siimesjarvi::new()),
-            Box::new(PlAgiAntti::new()),
-            Box::new(TwentyCenturyFox::new()),
-            Box::new(Aurelian::new()),
-            Box::new(Miklas::new()),
-        ];
+    /// Plays a batch of independent games, each one between the given players.
+    /// Returns the result of every game, or None for the games not played
+    /// because the championship was quit.
+    ///
+    /// Headless games are played concurrently, each one with fresh player
+    /// instances, and their outcomes are recorded in the order of the batch.
+    fn play_games(
+        &self,
+        session: &mut Session,
+        games: &[Vec<PlayerId>],
+    ) -> Vec<Option<GameResult>> {
+        if !self.headless {
+            return games
+                .iter()
+                .map(|players| self.play_game(session, players))
+                .collect();
+        }
+
+        let first_game_id = session.game_id + 1;
+        let entrants = &session.entrants;
+        let outcomes = games
+            .par_iter()
+            .enumerate()
+            .map(|(index, players)| {
+                let mut roster = entrants
+                    .iter()
+                    .filter(|(identity, _)| players.contains(&identity.id))
+                    .map(|(identity, factory)| (identity.clone(), factory()))
+                    .collect();
+                self.run_headless_game(first_game_id + index as u32, &mut roster)
+            })
+            .collect::<Vec<_>>();
+
+        session.game_id += games.len() as u32;
+        outcomes
+            .into_iter()
+            .map(|game_outcome| Some(Self::record_game(session, game_outcome)))
+            .collect()
+    }
+
+    /// Plays a game between the given players, unless the championship was quit
+    fn play_game(&self, session: &mut Session, players: &[PlayerId]) -> Option<GameResult> {
+        if session.quit {
+            return None;
+        }
+        session.game_id += 1;
+
+        let (mut selected, benched): (Vec<_>, Vec<_>) = std::mem::take(&mut session.roster)
+            .into_iter()
+            .partition(|(identity, _)| players.contains(&identity.id));
+
+        let (quit, game_outcome) = self.run_single_game(
+            session.game_id,
+            self.world_size.clone(),
+            &mut selected,
+            session.human.as_ref(),
+        );
+
+        // the players not keeping their state play the next game with a fresh instance
+        for (identity, player) in selected.iter_mut() {
+            if !player.keeps_state()
+                && let Some((_, factory)) = session
+                    .entrants
+                    .iter()
+                    .find(|(entrant, _)| entrant.id == identity.id)
+            {
+                *player = factory();
+            }
+        }
+
+        selected.extend(benched);
+        selected.sort_by_key(|(identity, _)| identity.id);
+        session.roster = selected;
+        session.quit = quit;
+
+        Some(Self::record_game(session, game_outcome))
+    }
+
+    fn record_game(session: &mut Session, game_outcome: GameOutcome) -> GameResult {
+        let result = (game_outcome.ranks().clone(), game_outcome.scores().clone());
+
+        session
+            .ratings
+            .update(&Self::named_ranks(&session.roster, &game_outcome));
+        println!("Game {} finished", game_outcome.game_id());
+        session.outcome.add_game_result(game_outcome);
 
         result
     }
+
+    /// Plays a batch of duels, each one over `rounds` games, where the winner
+    /// is the player winning more games. When the duels must be `decisive`,
+    /// equal wins are broken by the total score, and then in favour of the
+    /// first player. The duels not completed because the championship was quit
+    /// are left out.
+    fn play_matches(
+        &self,
+        session: &mut Session,
+        pairs: &[(PlayerId, PlayerId)],
+        rounds: u32,
+        decisive: bool,
+    ) -> Vec<MatchResult> {
+        let rounds = rounds.max(1) as usize;
+        let games = pairs
+            .iter()
+            .flat_map(|(a, b)| vec![vec![*a, *b]; rounds])
+            .collect::<Vec<_>>();
+        let game_results = self.play_games(session, &games);
+
+        let mut results = Vec::new();
+        for ((a, b), match_games) in pairs.iter().zip(game_results.chunks(rounds)) {
+            let Some(match_games) = match_games.iter().cloned().collect::<Option<Vec<_>>>() else {
+                continue;
+            };
+
+            let mut wins = (0, 0);
+            let mut scores = (0, 0);
+            for (ranks, game_scores) in match_games {
+                // a player missing from the ranks did not make it into the game
+                let rank_a = ranks.get(a).copied().unwrap_or(u8::MAX);
+                let rank_b = ranks.get(b).copied().unwrap_or(u8::MAX);
+                match rank_a.cmp(&rank_b) {
+                    Ordering::Less => wins.0 += 1,
+                    Ordering::Greater => wins.1 += 1,
+                    Ordering::Equal => {}
+                }
+                scores.0 += game_scores.get(a).copied().unwrap_or(0) as u32;
+                scores.1 += game_scores.get(b).copied().unwrap_or(0) as u32;
+            }
+
+            let winner = match wins.0.cmp(&wins.1) {
+                Ordering::Greater => Some(*a),
+                Ordering::Less => Some(*b),
+                Ordering::Equal if decisive => {
+                    if scores.1 > scores.0 {
+                        Some(*b)
+                    } else {
+                        Some(*a)
+                    }
+                }
+                Ordering::Equal => None,
+            };
+
+            let result = MatchResult {
+                players: (*a, *b),
+                wins,
+                winner,
+            };
+            session.outcome.add_match_result(result.clone());
+            results.push(result);
+        }
+
+        results
+    }
+
+    fn run_swiss(
+        &self,
+        session: &mut Session,
+        players: &[PlayerId],
+        swiss_rounds: u32,
+        rounds: u32,
+    ) {
+        let mut points: BTreeMap<PlayerId, f32> = players.iter().map(|id| (*id, 0.0)).collect();
+        let mut played = HashSet::new();
+        let mut had_bye = HashSet::new();
+
+        for _ in 0..swiss_rounds {
+            let order = standings(&Vec::from_iter(points.clone()), false)
+                .into_iter()
+                .map(|standing| standing.player_id)
+                .collect::<Vec<_>>();
+            let (pairs, bye) = swiss_pairs(&order, &played, &had_bye);
+
+            if let Some(player_id) = bye {
+                *points.entry(player_id).or_default() += 0.5;
+                had_bye.insert(player_id);
+            }
+            for result in self.play_matches(session, &pairs, rounds, false) {
+                Self::add_match_points(&mut points, &result);
+                played.insert(result.players);
+            }
+        }
+
+        session
+            .outcome
+            .set_standings(standings(&Vec::from_iter(points), false));
+    }
+
+    /// Runs an elimination bracket, where the players are out after `max_losses` lost duels
+    fn run_elimination(
+        &self,
+        session: &mut Session,
+        players: &[PlayerId],
+        max_losses: u32,
+        rounds: u32,
+    ) {
+        let mut losses: BTreeMap<PlayerId, u32> = players.iter().map(|id| (*id, 0)).collect();
+        let mut won: BTreeMap<PlayerId, u32> = players.iter().map(|id| (*id, 0)).collect();
+        // the later a player is eliminated, the better its standing
+        let mut eliminated_in: BTreeMap<PlayerId, u32> = BTreeMap::new();
+        let mut bracket_round = 0;
+
+        loop {
+            let alive = players
+                .iter()
+                .filter(|id| losses[id] < max_losses)
+                .map(|id| (*id, losses[id]))
+                .collect::<Vec<_>>();
+            if alive.len() < 2 || session.quit {
+                break;
+            }
+            bracket_round += 1;
+
+            let (pairs, _) = elimination_pairs(&alive);
+            for result in self.play_matches(session, &pairs, rounds, true) {
+                let (a, b) = result.players;
+                if let Some(winner) = result.winner {
+                    let loser = if winner == a { b } else { a };
+                    *won.entry(winner).or_default() += 1;
+                    *losses.entry(loser).or_default() += 1;
+                    if losses[&loser] >= max_losses {
+                        eliminated_in.insert(loser, bracket_round);
+                    }
+                }
+            }
+        }
+
+        let order = players
+            .iter()
+            .map(|id| {
+                let round = eliminated_in.get(id).copied().unwrap_or(u32::MAX);
+                (*id, round as f32)
+            })
+            .collect::<Vec<_>>();
+        let result = standings(&order, false)
+            .into_iter()
+            .map(|standing| Standing {
+                points: won[&standing.player_id] as f32,
+                ..standing
+            })
+            .collect();
+        session.outcome.set_standings(result);
+    }
+
+    fn add_match_points(points: &mut BTreeMap<PlayerId, f32>, result: &MatchResult) {
+        let (a, b) = result.players;
+        match result.winner {
+            Some(winner) => *points.entry(winner).or_default() += 1.0,
+            None => {
+                *points.entry(a).or_default() += 0.5;
+                *points.entry(b).or_default() += 0.5;
+            }
+        }
+    }
+
+    fn run_headless_game(&self, game_id: u32, roster: &mut Roster) -> GameOutcome {
+        let mut game = Game::new(
+            self.world_size.clone(),
+            game_seed(self.seed, game_id),
+            self.storage_directory.clone(),
+        );
+        game.spawn_players(std::mem::take(roster));
+
+        let outcome = game.run_headless(game_id);
+        self.write_logs(&game, game_id);
+
+        outcome
+    }
+
+    fn run_single_game(
+        &self,
+        game_id: u32,
+        world_size: WorldSize,
+        roster: &mut Roster,
+        human: Option<&(PlayerId, HumanPlayer)>,
+    ) -> (bool, GameOutcome) {
+        let mut game = Game::new(
+            world_size,
+            game_seed(self.seed, game_id),
+            self.storage_directory.clone(),
+        );
+        game.set_theme(self.theme);
+        game.spawn_players(std::mem::take(roster));
+        if let Some((player_id, human)) = human {
+            game.set_human_player(*player_id, human.clone());
+        }
+
+        let result = game.start(game_id);
+        self.write_logs(&game, game_id);
+        *roster = game.release_players();
+
+        result
+    }
+
+    fn write_logs(&self, game: &Game, game_id: u32) {
+        if let Some(directory) = &self.log_directory {
+            let directory = directory.join(format!("game_{game_id}"));
+            if let Err(e) = game.write_logs(&directory) {
+                panic!("Unable to write the logs to {}: {e}", directory.display());
+            }
+        }
+    }
+
+    /// Pairs the ranks of a game with the names of the players
+    fn named_ranks(
+        roster: &[(PlayerIdentity, Box<dyn Player>)],
+        game_outcome: &GameOutcome,
+    ) -> Vec<(String, u8)> {
+        roster
+            .iter()
+            .filter_map(|(identity, _)| {
+                game_outcome
+                    .ranks()
+                    .get(&identity.id)
+                    .map(|rank| (identity.name.clone(), *rank))
+            })
+            .collect()
+    }
+
+    /// Creates the players taking part in the championship, and allocates a
+    /// stable identity to every ready one. The same identities are then used
+    /// in all the games of the championship, regardless of whether some player
+    /// fails to initialize or to spawn in a particular game.
+    fn create_roster(&self) -> (Roster, Vec<(PlayerIdentity, PlayerFactory)>) {
+        self.players
+            .iter()
+            .map(|factory| (factory(), *factory))
+            .filter(|(player, _)| player.is_ready())
+            .enumerate()
+            .map(|(index, (player, factory))| {
+                let identity = PlayerIdentity {
+                    id: (index + 1) as PlayerId,
+                    name: player.name(),
+                    avatar: avatar(index + 1),
+                };
+                ((identity.clone(), player), (identity, factory))
+            })
+            .unzip()
+    }
+
+    /// Enters a player controlled from the keyboard, after the other players.
+    /// The returned handle of the player is used for handing the keys over to it.
+    fn enter_human_player(
+        roster: &mut Roster,
+        entrants: &mut Vec<(PlayerIdentity, PlayerFactory)>,
+    ) -> (PlayerId, HumanPlayer) {
+        let human = HumanPlayer::new();
+        let id = (roster.len() + 1) as PlayerId;
+        let identity = PlayerIdentity {
+            id,
+            name: human.name(),
+            avatar: avatar(id as usize),
+        };
+
+        roster.push((identity.clone(), Box::new(human.clone())));
+        entrants.push((identity, || Box::new(HumanPlayer::new())));
+        (id, human)
+    }
 }
@@ -1,9 +1,18 @@
+use std::collections::BTreeSet;
+
 use crate::{
     api::{
-        action::Action, context::Context as ApiContext, map_cell::Terrain, player::Details,
-        position::Position, rotation::Rotation, scan::ScanResult, world_size::WorldSize,
+        action::Action,
+        context::Context as ApiContext,
+        game::{DeathCause, GameRules, PlayerRng},
+        map_cell::Terrain,
+        player::{Details, PlayerId, DEAD_AVATAR},
+        position::Position,
+        rotation::Rotation,
+        scan::ScanResult,
+        world_size::WorldSize,
     },
-    engine::game::DEAD_AVATAR,
+    engine::event::GameEvent,
 };
 
 /// Private consts
@@ -33,7 +42,11 @@ impl Score {
 /// Represents the player context that the game engine is using for storing players state
 #[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Context {
+    /// The other players whose shells hit the player without killing it
+    assailants: BTreeSet<PlayerId>,
     cumulated_cpu_time: u128,
+    death_cause: Option<DeathCause>,
+    events: Vec<GameEvent>,
     health: u8,
     mobile: bool,
     previous_action: Action,
@@ -49,6 +62,7 @@ pub struct Context {
 impl Context {
     pub fn new(player_details: Details, position: Position, world_size: WorldSize) -> Self {
         Self {
+            assailants: BTreeSet::new(),
             health: 100,
             mobile: true,
             previous_action: Action::default(),
@@ -57,26 +71,47 @@ impl Context {
             scan: None,
             score: Score { value: 0 },
             cumulated_cpu_time: 0,
+            death_cause: None,
+            events: Vec::new(),
             turn: 0,
             world_size,
         }
     }
 
     pub fn damage_collision_forest(&mut self) {
-        self.generic_damage(DAMAGE_COLLISION_WITH_FOREST);
+        self.generic_damage(
+            DAMAGE_COLLISION_WITH_FOREST,
+            DeathCause::CollisionWithForest,
+        );
+    }
+
+    pub fn damage_collision_player(&mut self, other_id: PlayerId) {
+        self.generic_damage(
+            DAMAGE_COLLISION_WITH_PLAYER,
+            DeathCause::CollisionWithPlayer(other_id),
+        );
     }
 
-    pub fn damage_collision_player(&mut self, other: &mut Self) {
-        self.generic_damage(DAMAGE_COLLISION_WITH_PLAYER);
-        other.generic_damage(DAMAGE_COLLISION_WITH_PLAYER);
+    pub fn damage_direct_hit(&mut self, shooter_id: PlayerId) -> u16 {
+        self.hit_damage(
+            shooter_id,
+            DAMAGE_DIRECT_HIT,
+            SCORE_DIRECT_HIT_BONUS,
+            DeathCause::DirectHit(shooter_id),
+        )
     }
 
-    pub fn damage_direct_hit(&mut self, shooter_id: u8) -> u16 {
-        self.hit_damage(shooter_id, DAMAGE_DIRECT_HIT, SCORE_DIRECT_HIT_BONUS)
+    pub fn damage_indirect_hit(&mut self, shooter_id: PlayerId) -> u16 {
+        self.hit_damage(
+            shooter_id,
+            DAMAGE_INDIRECT_HIT,
+            SCORE_INDIRECT_HIT_BONUS,
+            DeathCause::IndirectHit(shooter_id),
+        )
     }
 
-    pub fn damage_indirect_hit(&mut self, shooter_id: u8) -> u16 {
-        self.hit_damage(shooter_id, DAMAGE_INDIRECT_HIT, SCORE_INDIRECT_HIT_BONUS)
+    pub fn death_cause(&self) -> Option<DeathCause> {
+        self.death_cause
     }
 
     pub fn reward_survivor(&mut self) {
@@ -103,6 +138,11 @@ impl Context {
         }
     }
 
+    /// The CPU time used by the player so far, in nanoseconds
+    pub fn cumulated_cpu_time(&self) -> u128 {
+        self.cumulated_cpu_time
+    }
+
     pub fn increase_cpu_time(&mut self, time: u128) {
         self.cumulated_cpu_time += time;
     }
@@ -127,8 +167,14 @@ impl Context {
         self.position = new_position.clone();
 
         match walk_on {
-            Terrain::Lake => self.generic_damage(DAMAGE_SINKING_INTO_LAKE),
-            Terrain::Swamp => self.mobile = false,
+            Terrain::Lake => self.generic_damage(DAMAGE_SINKING_INTO_LAKE, DeathCause::Drowning),
+            Terrain::Swamp if self.mobile => {
+                self.mobile = false;
+                self.events.push(GameEvent::Trapped {
+                    player_id: self.player_details.id,
+                    at: new_position.clone(),
+                });
+            }
             _ => {}
         }
     }
@@ -162,6 +208,11 @@ impl Context {
         self.turn = turn;
     }
 
+    /// Hands over the events concerning the player, since the previous call
+    pub fn take_events(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.events)
+    }
+
     pub fn turn(&self) -> usize {
         self.turn
     }
@@ -172,31 +223,84 @@ impl Context {
 }
 
 impl Context {
-    fn generic_damage(&mut self, amount: u16) {
+    fn generic_damage(&mut self, amount: u16, cause: DeathCause) {
+        let was_alive = self.health > 0;
+        let previous_health = self.health;
+
         self.health = self.health.saturating_sub(amount as u8);
         if self.health == 0 {
             self.player_details.avatar = DEAD_AVATAR;
             self.player_details.alive = false;
+
+            if was_alive {
+                self.death_cause = Some(cause);
+            }
+        }
+
+        if self.health < previous_health {
+            self.events.push(GameEvent::Damage {
+                player_id: self.player_details.id,
+                amount: previous_health - self.health,
+                cause,
+                fatal: self.health == 0,
+            });
         }
     }
 
-    fn hit_damage(&mut self, shooter_id: u8, damage_amount: u16, reward_amount: u16) -> u16 {
+    fn hit_damage(
+        &mut self,
+        shooter_id: PlayerId,
+        damage_amount: u16,
+        reward_amount: u16,
+        cause: DeathCause,
+    ) -> u16 {
         let mut reward = 0;
 
         if self.health > 0 {
-            self.generic_damage(damage_amount);
+            self.generic_damage(damage_amount, cause);
 
             if self.player_details.id != shooter_id {
                 reward += reward_amount;
 
                 if self.health == 0 {
                     reward += SCORE_KILLING_BONUS;
+                    self.credit_assists(shooter_id);
+                } else {
+                    self.assailants.insert(shooter_id);
                 }
             }
         }
 
         reward
     }
+
+    /// Credits the earlier hits of the other players than the killer as assists
+    fn credit_assists(&mut self, killer_id: PlayerId) {
+        for player_id in std::mem::take(&mut self.assailants) {
+            if player_id != killer_id {
+                self.events.push(GameEvent::Assist {
+                    player_id,
+                    victim_id: self.player_details.id,
+                });
+            }
+        }
+    }
+}
+
+/// Provides the rules that the engine enforces, for a game lasting `max_turns`
+pub fn game_rules(max_turns: usize) -> GameRules {
+    GameRules {
+        max_turns,
+        damage_direct_hit: DAMAGE_DIRECT_HIT,
+        damage_indirect_hit: DAMAGE_INDIRECT_HIT,
+        damage_collision_with_player: DAMAGE_COLLISION_WITH_PLAYER,
+        damage_collision_with_forest: DAMAGE_COLLISION_WITH_FOREST,
+        damage_sinking_into_lake: DAMAGE_SINKING_INTO_LAKE,
+        score_direct_hit: SCORE_DIRECT_HIT_BONUS,
+        score_indirect_hit: SCORE_INDIRECT_HIT_BONUS,
+        score_killing: SCORE_KILLING_BONUS,
+        score_survivor: SCORE_SURVIVOR_BONUS,
+    }
 }
 
 impl std::fmt::Display for Context {
@@ -216,13 +320,15 @@ impl std::fmt::Display for Context {
     }
 }
 
-impl Into<ApiContext> for Context {
-    fn into(self) -> ApiContext {
+impl Context {
+    /// The context shared with the player, which draws from the given RNG
+    pub fn to_api(&self, rng: PlayerRng) -> ApiContext {
         ApiContext::new(
             self.health,
             self.previous_action.clone(),
             self.player_details.clone(),
             self.position.clone(),
+            rng,
             self.scan.clone(),
             self.turn,
             self.world_size.clone(),
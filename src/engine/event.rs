@@ -0,0 +1,31 @@
+//! Notable events happening during a game, recorded turn by turn
+
+use crate::api::{aiming::Aiming, game::DeathCause, player::PlayerId, position::Position};
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GameEvent {
+    /// A player fired a shell
+    ShotFired {
+        shooter_id: PlayerId,
+        aim: Aiming,
+        from: Position,
+    },
+    /// A shell fired by a player exploded `at` the given position
+    ShellExploded { shooter_id: PlayerId, at: Position },
+    /// A player lost `amount` health, because of `cause`. The damage is `fatal`
+    /// when it killed the player.
+    Damage {
+        player_id: PlayerId,
+        amount: u8,
+        cause: DeathCause,
+        fatal: bool,
+    },
+    /// A player got stuck in a swamp, `at` the given position
+    Trapped { player_id: PlayerId, at: Position },
+    /// A player had hit `victim_id` earlier in the game, without killing it,
+    /// before another player killed it
+    Assist {
+        player_id: PlayerId,
+        victim_id: PlayerId,
+    },
+}
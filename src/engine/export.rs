@@ -0,0 +1,424 @@
+//! Machine-readable export of the championship results, as JSON and CSV,
+//! for the scripts and dashboards which would otherwise scrape the terminal
+
+use std::{fs, io, path::Path};
+
+use crate::{
+    api::game::GameRules,
+    engine::{
+        outcome::{ChampionshipOutcome, GameOutcome},
+        stats::PlayerStats,
+        tournament::Format,
+    },
+};
+
+impl ChampionshipOutcome {
+    /// Writes `championship.json`, `games.csv`, `players.csv`, the HTML
+    /// `report.html` and the replay of every game into `directory`, creating
+    /// it if needed
+    pub fn export(&self, directory: &Path) -> io::Result<()> {
+        let replays = directory.join("replays");
+        fs::create_dir_all(&replays)?;
+        for game in self.game_results() {
+            let file_name = format!("game_{}.html", game.game_id());
+            fs::write(replays.join(file_name), game.to_replay_html())?;
+        }
+        fs::write(directory.join("report.html"), self.to_html())?;
+        fs::write(directory.join("championship.json"), self.to_json())?;
+        fs::write(directory.join("games.csv"), self.games_csv())?;
+        fs::write(directory.join("players.csv"), self.players_csv())
+    }
+
+    /// The whole championship: its setup, standings, statistics, duels and games
+    pub fn to_json(&self) -> String {
+        let stats = self.get_stats();
+        let rating_changes = self.get_rating_changes();
+
+        let players = self.players().iter().map(|identity| {
+            let rating = rating_changes.get(&identity.id);
+            object(vec![
+                ("id", identity.id.to_string()),
+                ("name", string(&identity.name)),
+                ("avatar", string(&identity.avatar.to_string())),
+                (
+                    "rating_before",
+                    optional(rating.map(|change| change.before)),
+                ),
+                ("rating_after", optional(rating.map(|change| change.after))),
+                (
+                    "stats",
+                    stats_json(&stats.get(&identity.id).cloned().unwrap_or_default()),
+                ),
+            ])
+        });
+
+        let standings = self.get_standings().iter().map(|standing| {
+            object(vec![
+                ("position", standing.position.to_string()),
+                ("player_id", standing.player_id.to_string()),
+                ("points", standing.points.to_string()),
+            ])
+        });
+
+        let matches = self.get_matches().iter().map(|result| {
+            object(vec![
+                (
+                    "players",
+                    array([result.players.0, result.players.1].map(|id| id.to_string())),
+                ),
+                (
+                    "wins",
+                    array([result.wins.0, result.wins.1].map(|wins| wins.to_string())),
+                ),
+                ("winner", optional(result.winner)),
+            ])
+        });
+
+        let mut head_to_head = self.head_to_head().into_iter().collect::<Vec<_>>();
+        head_to_head.sort();
+        let head_to_head = head_to_head.into_iter().map(|((winner, loser), games)| {
+            object(vec![
+                ("winner", winner.to_string()),
+                ("loser", loser.to_string()),
+                ("games", games.to_string()),
+            ])
+        });
+
+        object(vec![
+            ("seed", self.seed().to_string()),
+            ("format", format_json(self.format())),
+            ("players", array(players)),
+            ("standings", array(standings)),
+            ("matches", array(matches)),
+            ("head_to_head", array(head_to_head)),
+            (
+                "games",
+                array(self.game_results().iter().map(GameOutcome::to_json)),
+            ),
+        ])
+    }
+
+    /// One line per player of every game
+    pub fn games_csv(&self) -> String {
+        let mut result = String::from(
+            "game_id,seed,width,height,map_fingerprint,turns,player_id,player_name,rank,score\n",
+        );
+
+        for game in self.game_results() {
+            for identity in game.roster() {
+                let line = [
+                    game.game_id().to_string(),
+                    game.seed().to_string(),
+                    game.world_size().x.to_string(),
+                    game.world_size().y.to_string(),
+                    format!("{:016x}", game.map_fingerprint()),
+                    game.turn_count().to_string(),
+                    identity.id.to_string(),
+                    csv_field(&identity.name),
+                    csv_optional(game.ranks().get(&identity.id)),
+                    csv_optional(game.scores().get(&identity.id)),
+                ];
+                result += &line.join(",");
+                result.push('\n');
+            }
+        }
+
+        result
+    }
+
+    /// One line per player, with its standing, rating and statistics
+    pub fn players_csv(&self) -> String {
+        let mut result = String::from(
+            "player_id,player_name,position,points,rating_before,rating_after,games,\
+             shots_fired,direct_hits,indirect_hits,accuracy,damage_dealt,damage_taken,\
+             kills,assists,deaths_by_shell,deaths_by_lake,deaths_by_collision,turns_survived,\
+             cpu_time_us,idle,fire,moves,rotate,scan\n",
+        );
+
+        let stats = self.get_stats();
+        let rating_changes = self.get_rating_changes();
+
+        for identity in self.players() {
+            let standing = self
+                .get_standings()
+                .iter()
+                .find(|standing| standing.player_id == identity.id);
+            let rating = rating_changes.get(&identity.id);
+            let s = stats.get(&identity.id).cloned().unwrap_or_default();
+
+            let line = [
+                identity.id.to_string(),
+                csv_field(&identity.name),
+                csv_optional(standing.map(|standing| standing.position)),
+                csv_optional(standing.map(|standing| standing.points)),
+                csv_optional(rating.map(|change| change.before)),
+                csv_optional(rating.map(|change| change.after)),
+                s.games.to_string(),
+                s.shots_fired.to_string(),
+                s.direct_hits.to_string(),
+                s.indirect_hits.to_string(),
+                s.accuracy().to_string(),
+                s.damage_dealt.to_string(),
+                s.damage_taken.to_string(),
+                s.kills.to_string(),
+                s.assists.to_string(),
+                s.deaths_by_shell.to_string(),
+                s.deaths_by_lake.to_string(),
+                s.deaths_by_collision.to_string(),
+                s.turns_survived.to_string(),
+                s.cpu_time.as_micros().to_string(),
+                s.actions.idle.to_string(),
+                s.actions.fire.to_string(),
+                s.actions.moves.to_string(),
+                s.actions.rotate.to_string(),
+                s.actions.scan.to_string(),
+            ];
+            result += &line.join(",");
+            result.push('\n');
+        }
+
+        result
+    }
+}
+
+impl GameOutcome {
+    /// A single game: its setup, and the rank, score and statistics of every player
+    pub fn to_json(&self) -> String {
+        let stats = self.stats();
+
+        let players = self.roster().iter().map(|identity| {
+            object(vec![
+                ("id", identity.id.to_string()),
+                ("name", string(&identity.name)),
+                ("rank", optional(self.ranks().get(&identity.id))),
+                ("score", optional(self.scores().get(&identity.id))),
+                (
+                    "stats",
+                    stats_json(&stats.get(&identity.id).cloned().unwrap_or_default()),
+                ),
+            ])
+        });
+
+        object(vec![
+            ("game_id", self.game_id().to_string()),
+            ("seed", self.seed().to_string()),
+            (
+                "world_size",
+                object(vec![
+                    ("x", self.world_size().x.to_string()),
+                    ("y", self.world_size().y.to_string()),
+                ]),
+            ),
+            (
+                "map_fingerprint",
+                string(&format!("{:016x}", self.map_fingerprint())),
+            ),
+            ("rules", rules_json(self.rules())),
+            ("turns", self.turn_count().to_string()),
+            ("players", array(players)),
+        ])
+    }
+}
+
+// Private functions
+
+fn stats_json(stats: &PlayerStats) -> String {
+    object(vec![
+        ("games", stats.games.to_string()),
+        ("shots_fired", stats.shots_fired.to_string()),
+        ("direct_hits", stats.direct_hits.to_string()),
+        ("indirect_hits", stats.indirect_hits.to_string()),
+        ("accuracy", stats.accuracy().to_string()),
+        ("damage_dealt", stats.damage_dealt.to_string()),
+        ("damage_taken", stats.damage_taken.to_string()),
+        ("kills", stats.kills.to_string()),
+        ("assists", stats.assists.to_string()),
+        ("deaths_by_shell", stats.deaths_by_shell.to_string()),
+        ("deaths_by_lake", stats.deaths_by_lake.to_string()),
+        ("deaths_by_collision", stats.deaths_by_collision.to_string()),
+        ("turns_survived", stats.turns_survived.to_string()),
+        ("cpu_time_us", stats.cpu_time.as_micros().to_string()),
+        (
+            "actions",
+            object(vec![
+                ("idle", stats.actions.idle.to_string()),
+                ("fire", stats.actions.fire.to_string()),
+                ("moves", stats.actions.moves.to_string()),
+                ("rotate", stats.actions.rotate.to_string()),
+                ("scan", stats.actions.scan.to_string()),
+            ]),
+        ),
+    ])
+}
+
+fn rules_json(rules: &GameRules) -> String {
+    object(vec![
+        ("max_turns", rules.max_turns.to_string()),
+        ("damage_direct_hit", rules.damage_direct_hit.to_string()),
+        ("damage_indirect_hit", rules.damage_indirect_hit.to_string()),
+        (
+            "damage_collision_with_player",
+            rules.damage_collision_with_player.to_string(),
+        ),
+        (
+            "damage_collision_with_forest",
+            rules.damage_collision_with_forest.to_string(),
+        ),
+        (
+            "damage_sinking_into_lake",
+            rules.damage_sinking_into_lake.to_string(),
+        ),
+        ("score_direct_hit", rules.score_direct_hit.to_string()),
+        ("score_indirect_hit", rules.score_indirect_hit.to_string()),
+        ("score_killing", rules.score_killing.to_string()),
+        ("score_survivor", rules.score_survivor.to_string()),
+    ])
+}
+
+fn format_json(format: &Format) -> String {
+    let mut fields = vec![];
+    match format {
+        Format::FreeForAll => fields.push(("type", string("free_for_all"))),
+        Format::RandomSubsets { size } => {
+            fields.push(("type", string("random_subsets")));
+            fields.push(("size", size.to_string()));
+        }
+        Format::RoundRobin => fields.push(("type", string("round_robin"))),
+        Format::Swiss { rounds } => {
+            fields.push(("type", string("swiss")));
+            fields.push(("rounds", rounds.to_string()));
+        }
+        Format::SingleElimination => fields.push(("type", string("single_elimination"))),
+        Format::DoubleElimination => fields.push(("type", string("double_elimination"))),
+    }
+
+    object(fields)
+}
+
+pub(super) fn object(fields: Vec<(&str, String)>) -> String {
+    let fields = fields
+        .into_iter()
+        .map(|(key, value)| format!("{}:{value}", string(key)))
+        .collect::<Vec<_>>();
+    format!("{{{}}}", fields.join(","))
+}
+
+pub(super) fn array(items: impl IntoIterator<Item = String>) -> String {
+    format!("[{}]", items.into_iter().collect::<Vec<_>>().join(","))
+}
+
+pub(super) fn string(value: &str) -> String {
+    let mut result = String::from('"');
+    for c in value.chars() {
+        match c {
+            '"' => result += "\\\"",
+            '\\' => result += "\\\\",
+            '\n' => result += "\\n",
+            '\r' => result += "\\r",
+            '\t' => result += "\\t",
+            // keeps the JSON safe to embed into the script of an HTML page
+            '<' => result += "\\u003c",
+            c if (c as u32) < 0x20 => result += &format!("\\u{:04x}", c as u32),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map_or("null".to_string(), |value| value.to_string())
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn csv_optional<T: ToString>(value: Option<T>) -> String {
+    value.map_or(String::new(), |value| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::{
+            map_cell::MapCell,
+            player::PlayerIdentity,
+            world_size::{WorldSize, MAX_WORLD_SIZE},
+        },
+        engine::context::game_rules,
+    };
+
+    fn identity(id: u8, name: &str) -> PlayerIdentity {
+        PlayerIdentity {
+            id,
+            name: name.to_string(),
+            avatar: '🙂',
+        }
+    }
+
+    #[test]
+    fn test_strings_are_escaped() {
+        assert_eq!(r#""say \"hi\"\n""#, string("say \"hi\"\n"));
+        assert_eq!("\"Smith, \"\"J\"\"\"", csv_field("Smith, \"J\""));
+        assert_eq!("Alice", csv_field("Alice"));
+    }
+
+    #[test]
+    fn test_formats_are_exported() {
+        assert_eq!(r#"{"type":"free_for_all"}"#, format_json(&Format::FreeForAll));
+        assert_eq!(
+            r#"{"type":"random_subsets","size":4}"#,
+            format_json(&Format::RandomSubsets { size: 4 })
+        );
+        assert_eq!(
+            r#"{"type":"swiss","rounds":3}"#,
+            format_json(&Format::Swiss { rounds: 3 })
+        );
+    }
+
+    #[test]
+    fn test_games_are_exported() {
+        let roster = vec![identity(1, "Alice"), identity(2, "Bob, Jr")];
+        let mut outcome = ChampionshipOutcome::new();
+        for identity in &roster {
+            outcome.register_player(identity.clone());
+        }
+
+        let mut game = GameOutcome::new(
+            1,
+            42,
+            WorldSize { x: 3, y: 2 },
+            game_rules(10),
+            Box::new([[MapCell::Unallocated; MAX_WORLD_SIZE]; MAX_WORLD_SIZE]),
+            roster,
+        );
+        game.add_player_rank(1, 1);
+        game.add_player_score(1, 30);
+        game.add_player_rank(2, 2);
+        game.add_player_score(2, 10);
+        let fingerprint = format!("{:016x}", game.map_fingerprint());
+        outcome.add_game_result(game);
+
+        let csv = outcome.games_csv();
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(3, lines.len());
+        assert_eq!(format!("1,42,3,2,{fingerprint},0,1,Alice,1,30"), lines[1]);
+        assert_eq!(
+            format!("1,42,3,2,{fingerprint},0,2,\"Bob, Jr\",2,10"),
+            lines[2]
+        );
+
+        let json = outcome.to_json();
+        assert!(json.contains(r#""seed":42,"world_size":{"x":3,"y":2}"#));
+        assert!(json.contains(r#""max_turns":10"#));
+        assert!(json.contains(r#""name":"Bob, Jr","rank":2,"score":10"#));
+
+        assert_eq!(3, outcome.players_csv().lines().count());
+    }
+}
@@ -0,0 +1,216 @@
+//! Narration of the notable events of a game, shown as a scrolling list in the
+//! side panel of the live viewer
+
+use std::collections::VecDeque;
+
+use crate::{
+    api::{game::DeathCause, player::PlayerId},
+    engine::event::GameEvent,
+};
+
+// The amount of narrated events kept, enough for stepping back through the
+// last turns
+const FEED_CAPACITY: usize = 100;
+
+/// The last narrated events, along with the turn they happened in
+#[derive(Debug, Default)]
+pub struct EventFeed {
+    entries: VecDeque<(usize, GameEvent)>,
+}
+
+impl EventFeed {
+    /// Keeps the events of a turn worth telling: the deaths, drownings,
+    /// collisions and swamp traps. The hits which did not kill are left out.
+    pub fn record(&mut self, turn: usize, events: &[GameEvent]) {
+        for event in events {
+            if narrate(event, |_| String::new()).is_none() || is_told_twice(event, events) {
+                continue;
+            }
+
+            if self.entries.len() == FEED_CAPACITY {
+                self.entries.pop_front();
+            }
+            self.entries.push_back((turn, event.clone()));
+        }
+    }
+
+    /// Narrates up to `count` of the last events happened until the given
+    /// turn, the most recent one last. The players are named by `name`.
+    pub fn last_lines(
+        &self,
+        count: usize,
+        until_turn: usize,
+        name: impl Fn(PlayerId) -> String,
+    ) -> Vec<String> {
+        let mut result = self
+            .entries
+            .iter()
+            .rev()
+            .filter(|(turn, _)| *turn <= until_turn)
+            .filter_map(|(turn, event)| narrate(event, &name).map(|text| format!("T{turn} {text}")))
+            .take(count)
+            .collect::<Vec<_>>();
+        result.reverse();
+        result
+    }
+}
+
+/// Tells what happened, in a few words
+fn narrate(event: &GameEvent, name: impl Fn(PlayerId) -> String) -> Option<String> {
+    let text = match event {
+        GameEvent::Damage {
+            player_id,
+            cause,
+            fatal: true,
+            ..
+        } => {
+            let victim = name(*player_id);
+            let (killer_id, how) = match cause {
+                DeathCause::DirectHit(id) => (*id, "direct hit"),
+                DeathCause::IndirectHit(id) => (*id, "indirect hit"),
+                DeathCause::CollisionWithPlayer(id) => (*id, "collision"),
+                DeathCause::CollisionWithForest => {
+                    return Some(format!("{victim} crashed into a forest, and died"));
+                }
+                DeathCause::Drowning => return Some(format!("{victim} drowned")),
+            };
+
+            if killer_id == *player_id {
+                format!("{victim} killed itself ({how})")
+            } else {
+                format!("{} killed {victim} ({how})", name(killer_id))
+            }
+        }
+        GameEvent::Damage {
+            player_id, cause, ..
+        } => match cause {
+            DeathCause::DirectHit(_) | DeathCause::IndirectHit(_) => return None,
+            DeathCause::CollisionWithPlayer(id) => {
+                format!("{} collided with {}", name(*player_id), name(*id))
+            }
+            DeathCause::CollisionWithForest => {
+                format!("{} crashed into a forest", name(*player_id))
+            }
+            DeathCause::Drowning => format!("{} fell into a lake", name(*player_id)),
+        },
+        GameEvent::Trapped { player_id, .. } => {
+            format!("{} got stuck in a swamp", name(*player_id))
+        }
+        GameEvent::ShotFired { .. }
+        | GameEvent::ShellExploded { .. }
+        | GameEvent::Assist { .. } => return None,
+    };
+
+    Some(text)
+}
+
+/// Both tanks of a collision are damaged, but the collision is told once: as a
+/// kill when it killed either tank, otherwise by the tank with the lowest id
+fn is_told_twice(event: &GameEvent, events: &[GameEvent]) -> bool {
+    let GameEvent::Damage {
+        player_id,
+        cause: DeathCause::CollisionWithPlayer(other_id),
+        fatal: false,
+        ..
+    } = event
+    else {
+        return false;
+    };
+
+    events.iter().any(|other| {
+        matches!(
+            other,
+            GameEvent::Damage {
+                player_id: id,
+                cause: DeathCause::CollisionWithPlayer(with),
+                fatal,
+                ..
+            } if id == other_id && with == player_id && (*fatal || id < player_id)
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::position::Position;
+
+    fn damage(player_id: PlayerId, cause: DeathCause, fatal: bool) -> GameEvent {
+        GameEvent::Damage {
+            player_id,
+            amount: 25,
+            cause,
+            fatal,
+        }
+    }
+
+    fn name(player_id: PlayerId) -> String {
+        ['🦊', '🐼', '🤖'][player_id as usize - 1].to_string()
+    }
+
+    #[test]
+    fn test_notable_events_are_narrated() {
+        let mut feed = EventFeed::default();
+        feed.record(
+            143,
+            &[
+                GameEvent::ShotFired {
+                    shooter_id: 1,
+                    aim: Default::default(),
+                    from: Position { x: 1, y: 1 },
+                },
+                damage(3, DeathCause::IndirectHit(1), false),
+                damage(2, DeathCause::DirectHit(1), true),
+            ],
+        );
+        feed.record(
+            150,
+            &[
+                damage(3, DeathCause::Drowning, true),
+                GameEvent::Trapped {
+                    player_id: 1,
+                    at: Position { x: 2, y: 2 },
+                },
+            ],
+        );
+
+        assert_eq!(
+            vec![
+                "T143 🦊 killed 🐼 (direct hit)",
+                "T150 🤖 drowned",
+                "T150 🦊 got stuck in a swamp",
+            ],
+            feed.last_lines(5, 150, name)
+        );
+        // the events after the displayed turn are hidden, while stepping back
+        assert_eq!(
+            vec!["T143 🦊 killed 🐼 (direct hit)"],
+            feed.last_lines(5, 149, name)
+        );
+        assert_eq!(1, feed.last_lines(1, 150, name).len());
+    }
+
+    #[test]
+    fn test_collisions_are_told_once() {
+        let mut feed = EventFeed::default();
+        feed.record(
+            7,
+            &[
+                damage(3, DeathCause::CollisionWithPlayer(1), false),
+                damage(1, DeathCause::CollisionWithPlayer(3), false),
+            ],
+        );
+        feed.record(
+            8,
+            &[
+                damage(2, DeathCause::CollisionWithPlayer(3), false),
+                damage(3, DeathCause::CollisionWithPlayer(2), true),
+            ],
+        );
+
+        assert_eq!(
+            vec!["T7 🦊 collided with 🤖", "T8 🐼 killed 🤖 (collision)"],
+            feed.last_lines(5, 8, name)
+        );
+    }
+}
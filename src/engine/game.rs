@@ -1,20 +1,35 @@
 use crate::{
     api::{
-        player::{Avatar, Player},
+        game::GameEnd,
+        player::{Avatar, Player, PlayerId, PlayerIdentity},
         world_size::WorldSize,
     },
-    engine::{outcome::GameOutcome, world::World},
+    engine::{
+        human::HumanPlayer, outcome::GameOutcome, theme::Theme, viewport::visible_cells,
+        world::World,
+    },
     terminal::{get_terminal, Terminal, CHAMPIONSHIP_MODE},
 };
 
-use crossterm::event::{poll, read, Event, KeyCode};
+use crossterm::event::{poll, read, Event, KeyCode, KeyEvent};
 
-use std::time::Duration;
+use std::{
+    io,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
 
 const ENABLE_SHELL_ANIMATION: bool = true;
 const GAME_TICK_DURATION_MSEC: u64 = 5;
+// The amount of cells scrolled by every arrow key press
+const SCROLL_STEP: isize = 4;
+// The delay after which a typed digit starts a new player id
+const SELECTION_TIMEOUT: Duration = Duration::from_millis(1000);
+// The time given to the human players for choosing their action, every turn
+const HUMAN_TURN_TIMEOUT: Duration = Duration::from_secs(10);
+// The interval at which the keys are polled, while waiting for a human player
+const INPUT_POLL_INTERVAL: Duration = Duration::from_millis(10);
 
-pub const DEAD_AVATAR: Avatar = '💀';
 const DEFAULT_AVATAR: Avatar = '👶';
 const AVATARS: [Avatar; 18] = [
     '🙂', '😈', '👽', '🤡', '🤖', '🎃', '🐶', '🐺', '🐻', '🐼', '🦊', '🐵', '🐷', '🐱', '🦁', '🐰',
@@ -22,73 +37,216 @@ const AVATARS: [Avatar; 18] = [
 ];
 
 pub struct Game {
+    benched: Vec<(PlayerIdentity, Box<dyn Player>)>,
+    // only the live games enter the raw mode of the terminal
+    raw_mode: bool,
+    roster: Vec<PlayerIdentity>,
+    storage_directory: PathBuf,
     world: Box<World>,
 }
 
 impl Game {
-    pub fn new(world_size: WorldSize) -> Self {
+    pub fn new(world_size: WorldSize, seed: u64, storage_directory: PathBuf) -> Self {
         Self {
+            benched: Vec::new(),
+            raw_mode: false,
+            roster: Vec::new(),
+            storage_directory,
             world: Box::new(World::new(
                 ENABLE_SHELL_ANIMATION,
                 GAME_TICK_DURATION_MSEC,
                 world_size,
+                seed,
             )),
         }
     }
 
+    /// Marks the spawned tank of the given player as controlled from the
+    /// keyboard, through the given handle of the player
+    pub fn set_human_player(&mut self, player_id: PlayerId, human: HumanPlayer) {
+        self.world.set_human_player(player_id, human);
+    }
+
+    /// Selects how the map is rendered in the live viewer
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.world.set_theme(theme);
+    }
+
     pub fn start(&mut self, game_id: u32) -> (bool, GameOutcome) {
+        for _ in 0..self.world.rejected_sea_worlds() {
+            println!("Rejecting sea world...");
+        }
+
         Terminal::enter_raw_mode();
+        self.raw_mode = true;
 
         let mut terminal = get_terminal().lock().unwrap();
         terminal.clear_screen();
-        terminal.println(self.world.to_string());
+        self.world.set_visible_cells(visible_cells(&self.world.size()));
+        terminal.draw(self.world.render());
+
+        self.world
+            .notify_game_start(game_id, &self.roster, &self.storage_directory);
+
+        // a human player plays from the inspector of its tank, and aims on the
+        // debug overlay, which is hidden in a new world
+        if let Some(player_id) = self.world.human_player() {
+            self.world.select_tank(Some(player_id));
+            self.world.toggle_overlay();
+        }
 
         let mut pause = false;
         let mut next = false;
         let mut quit = false;
         let mut animation = ENABLE_SHELL_ANIMATION;
         let mut tick_ms = GAME_TICK_DURATION_MSEC;
+        let mut selection = String::new();
+        let mut last_digit = Instant::now();
+        let mut waiting_since = Instant::now();
 
-        let mut game_outcome = GameOutcome::new(game_id, self.world.map());
+        let mut game_outcome = self.new_outcome(game_id);
 
         while !self.world.is_game_over() {
+            // the live view follows the resizing of the terminal
+            self.world.set_visible_cells(visible_cells(&self.world.size()));
+
             if !CHAMPIONSHIP_MODE {
-                if let Ok(true) = poll(Duration::from_millis(0)) {
-                    if let Ok(event) = read() {
-                        if event == Event::Key(KeyCode::Esc.into()) {
-                            quit = true;
-                            break;
-                        } else if event == Event::Key(KeyCode::Up.into()) {
-                            tick_ms = tick_ms.saturating_add(1);
-                            self.world.update_tick(tick_ms);
-                        } else if event == Event::Key(KeyCode::Down.into()) {
-                            tick_ms = tick_ms.saturating_sub(1);
-                            self.world.update_tick(tick_ms);
-                        } else if event == Event::Key(KeyCode::Char('a').into())
-                            || event == Event::Key(KeyCode::Char('A').into())
-                        {
-                            animation = !animation;
-                            self.world.update_animation(animation);
-                        } else if event == Event::Key(KeyCode::Char('n').into())
-                            || event == Event::Key(KeyCode::Char('N').into())
-                        {
-                            pause = false;
-                            next = true;
-                        } else if event == Event::Key(KeyCode::Char('p').into())
-                            || event == Event::Key(KeyCode::Char('P').into())
-                        {
-                            pause = !pause;
+                let mut redraw = false;
+                let waiting = !pause
+                    && self.world.awaits_input()
+                    && waiting_since.elapsed() < HUMAN_TURN_TIMEOUT;
+                let timeout = if waiting {
+                    INPUT_POLL_INTERVAL
+                } else {
+                    Duration::ZERO
+                };
+
+                if let Ok(true) = poll(timeout)
+                    && let Ok(event) = read()
+                {
+                    // the human players get the first pick of the keys
+                    let used = match event {
+                        Event::Key(key) if waiting => self.world.on_key(key),
+                        _ => false,
+                    };
+
+                    if used {
+                        redraw = true;
+                    } else if event == Event::Key(KeyCode::Esc.into()) {
+                        quit = true;
+                        break;
+                    } else if event == Event::Key(KeyCode::Char('+').into())
+                        || event == Event::Key(KeyCode::Char('=').into())
+                    {
+                        tick_ms = tick_ms.saturating_add(1);
+                        self.world.update_tick(tick_ms);
+                        redraw = true;
+                    } else if event == Event::Key(KeyCode::Char('-').into()) {
+                        tick_ms = tick_ms.saturating_sub(1);
+                        self.world.update_tick(tick_ms);
+                        redraw = true;
+                    } else if event == Event::Key(KeyCode::Up.into()) {
+                        self.world.scroll(0, -SCROLL_STEP);
+                        redraw = true;
+                    } else if event == Event::Key(KeyCode::Down.into()) {
+                        self.world.scroll(0, SCROLL_STEP);
+                        redraw = true;
+                    } else if event == Event::Key(KeyCode::Left.into()) {
+                        self.world.scroll(-SCROLL_STEP, 0);
+                        redraw = true;
+                    } else if event == Event::Key(KeyCode::Right.into()) {
+                        self.world.scroll(SCROLL_STEP, 0);
+                        redraw = true;
+                    } else if event == Event::Key(KeyCode::Char('f').into())
+                        || event == Event::Key(KeyCode::Char('F').into())
+                    {
+                        self.world.follow_next_tank();
+                        redraw = true;
+                    } else if event == Event::Key(KeyCode::Char('m').into())
+                        || event == Event::Key(KeyCode::Char('M').into())
+                    {
+                        self.world.toggle_minimap();
+                        redraw = true;
+                    } else if let Event::Key(KeyEvent {
+                        code: KeyCode::Char(digit @ '0'..='9'),
+                        ..
+                    }) = event
+                    {
+                        // the digits typed in a quick succession form a player id
+                        if last_digit.elapsed() > SELECTION_TIMEOUT {
+                            selection.clear();
+                        }
+                        selection.push(digit);
+                        last_digit = Instant::now();
+                        if let Ok(player_id) = selection.parse() {
+                            self.world.select_tank(Some(player_id));
                         }
+                        redraw = true;
+                    } else if event == Event::Key(KeyCode::Char('i').into())
+                        || event == Event::Key(KeyCode::Char('I').into())
+                    {
+                        self.world.select_tank(None);
+                        redraw = true;
+                    } else if event == Event::Key(KeyCode::Char('b').into())
+                        || event == Event::Key(KeyCode::Char('B').into())
+                    {
+                        self.world.toggle_belief_map();
+                        redraw = true;
+                    } else if event == Event::Key(KeyCode::Char('o').into())
+                        || event == Event::Key(KeyCode::Char('O').into())
+                    {
+                        self.world.toggle_overlay();
+                        redraw = true;
+                    } else if event == Event::Key(KeyCode::Char(',').into())
+                        || event == Event::Key(KeyCode::Char('<').into())
+                    {
+                        // past turns are only viewed while the game is paused
+                        if self.world.step_back() {
+                            pause = true;
+                        }
+                        redraw = true;
+                    } else if event == Event::Key(KeyCode::Char('.').into())
+                        || event == Event::Key(KeyCode::Char('>').into())
+                    {
+                        self.world.step_forward();
+                        redraw = true;
+                    } else if event == Event::Key(KeyCode::Char('a').into())
+                        || event == Event::Key(KeyCode::Char('A').into())
+                    {
+                        animation = !animation;
+                        self.world.update_animation(animation);
+                    } else if event == Event::Key(KeyCode::Char('n').into())
+                        || event == Event::Key(KeyCode::Char('N').into())
+                    {
+                        pause = false;
+                        next = true;
+                    } else if event == Event::Key(KeyCode::Char('p').into())
+                        || event == Event::Key(KeyCode::Char('P').into())
+                    {
+                        pause = !pause;
                     }
                 }
 
                 if pause {
+                    // the view still follows the keys while the game is paused
+                    if redraw {
+                        terminal.draw(self.world.render());
+                    }
+                    waiting_since = Instant::now();
+                    continue;
+                }
+
+                if waiting && self.world.awaits_input() {
+                    if redraw {
+                        terminal.draw(self.world.render());
+                    }
                     continue;
                 }
             }
 
-            let turn_outcome = self.world.new_turn(&mut terminal);
+            let turn_outcome = self.world.new_turn(Some(&mut terminal));
             game_outcome.add_turn_outcome(turn_outcome);
+            waiting_since = Instant::now();
 
             if next {
                 pause = true;
@@ -96,17 +254,95 @@ impl Game {
             }
         }
 
+        self.world.return_to_present();
         terminal.move_caret_to_origin();
         terminal.clear_below();
 
         terminal.println("[Final game state]\n");
         terminal.println(&self.world);
 
+        self.finish(game_id, &mut game_outcome);
+
+        (quit, game_outcome)
+    }
+
+    /// Plays the whole game at once, without any rendering nor delays
+    pub fn run_headless(&mut self, game_id: u32) -> GameOutcome {
+        self.world
+            .notify_game_start(game_id, &self.roster, &self.storage_directory);
+
+        let mut game_outcome = self.new_outcome(game_id);
+
+        while !self.world.is_game_over() {
+            let turn_outcome = self.world.new_turn(None);
+            game_outcome.add_turn_outcome(turn_outcome);
+        }
+
+        self.finish(game_id, &mut game_outcome);
+
+        game_outcome
+    }
+
+    /// Writes the log of every player of the game into `directory`, one file
+    /// per player
+    pub fn write_logs(&self, directory: &Path) -> io::Result<()> {
+        self.world.write_logs(directory, &self.roster)
+    }
+
+    /// Spawns the players into the game world, under the identities allocated
+    /// by the championship. Only the successfully spawned players are part of
+    /// the game roster.
+    pub fn spawn_players(&mut self, players: Vec<(PlayerIdentity, Box<dyn Player>)>) {
+        for (identity, player) in players {
+            if player.name() != identity.name {
+                panic!(
+                    "Player \"{}\" cannot be spawned under identity {identity}!",
+                    player.name()
+                );
+            }
+
+            match self.world.spawn_player(player, &identity) {
+                Ok(()) => self.roster.push(identity),
+                Err(player) => self.benched.push((identity, player)),
+            }
+        }
+    }
+
+    /// Hands back all the players of the game, including the ones which could
+    /// not be spawned, sorted by their id.
+    pub fn release_players(&mut self) -> Vec<(PlayerIdentity, Box<dyn Player>)> {
+        let mut players = std::mem::take(&mut self.benched);
+
+        for (player_id, player) in self.world.release_players() {
+            if let Some(identity) = self.roster.iter().find(|entry| entry.id == player_id) {
+                players.push((identity.clone(), player));
+            }
+        }
+        players.sort_by_key(|(identity, _)| identity.id);
+
+        players
+    }
+}
+
+// Private functions
+impl Game {
+    fn new_outcome(&self, game_id: u32) -> GameOutcome {
+        GameOutcome::new(
+            game_id,
+            self.world.seed(),
+            self.world.size(),
+            self.world.rules(),
+            self.world.map(),
+            self.roster.clone(),
+        )
+    }
+
+    /// Ranks the players, and lets them know about the result
+    fn finish(&mut self, game_id: u32, game_outcome: &mut GameOutcome) {
         self.world.reward_survivors();
 
         let mut players = self.world.get_ready_players();
-        players.sort_by(|&a, &b| a.context().score().cmp(&b.context().score()));
-        players.reverse();
+        players.sort_by_key(|tank| std::cmp::Reverse(tank.context().score()));
 
         let mut rank = 0;
         let mut score = u16::MAX;
@@ -117,15 +353,15 @@ impl Game {
             }
 
             game_outcome.add_player_rank(tank.context().player_details().id, rank);
+            game_outcome
+                .add_player_score(tank.context().player_details().id, tank.context().score());
         }
 
-        (quit, game_outcome)
-    }
-
-    pub fn spawn_players(&mut self, players: Vec<Box<dyn Player>>) {
-        for (rank, player) in players.into_iter().enumerate() {
-            self.world.spawn_player(player, avatar(rank + 1));
-        }
+        self.world.notify_game_end(&GameEnd {
+            game_id,
+            ranks: game_outcome.ranks().clone(),
+            scores: game_outcome.scores().clone(),
+        });
     }
 }
 
@@ -140,7 +376,9 @@ pub fn avatar(player_id: usize) -> Avatar {
 
 impl Drop for Game {
     fn drop(&mut self) {
-        Terminal::exit_raw_mode();
+        if self.raw_mode {
+            Terminal::exit_raw_mode();
+        }
     }
 }
 
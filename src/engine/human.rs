@@ -0,0 +1,296 @@
+//! A player controlled from the keyboard, for playing against the bots by hand
+
+use std::sync::{Arc, Mutex};
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::api::{
+    action::Action,
+    aiming::Aiming,
+    context::Context,
+    debug_overlay::DebugOverlay,
+    direction::Direction,
+    game::GameStart,
+    orientation::Orientation,
+    player::Player,
+    position::{Position, POSITIONAL_SHOT_DISTANCE},
+    rotation::Rotation,
+    scan::ScanType,
+    world_size::WorldSize,
+};
+
+/// The keys of a human player, as listed in the inspector of its tank
+pub const KEYS_HELP: [&str; 4] = [
+    "W / S  - Move forward / backward",
+    "A / D  - Rotate counter-clockwise / clockwise",
+    "Q / E  - Omni scan / Mono scan ahead, X - Idle",
+    "Space  - Fire ahead, T - Aim with the arrows, Enter - Fire",
+];
+
+/// The next action chosen by the human. The ones depending on the facing of
+/// the tank are resolved when the turn is played, since the tank may have
+/// turned after the keys were pressed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Command {
+    Act(Action),
+    FireAhead,
+    ScanAhead,
+}
+
+/// A player controlled from the keyboard. The championship keeps a clone of
+/// it, through which the engine hands over the keys pressed in the live view.
+#[derive(Clone, Default)]
+pub struct HumanPlayer {
+    controls: Arc<Mutex<Controls>>,
+}
+
+/// The keyboard state of a human player
+struct Controls {
+    command: Option<Command>,
+    /// The target of a positional shot, while aiming
+    cursor: Option<Position>,
+    position: Option<Position>,
+    world_size: WorldSize,
+}
+
+impl HumanPlayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Default for Controls {
+    fn default() -> Self {
+        Self {
+            command: None,
+            cursor: None,
+            position: None,
+            world_size: WorldSize { x: 0, y: 0 },
+        }
+    }
+}
+
+impl Player for HumanPlayer {
+    fn on_game_start(&mut self, game: GameStart) {
+        let mut controls = self.controls.lock().unwrap();
+        *controls = Controls::default();
+        controls.world_size = game.world_size;
+    }
+
+    fn act(&mut self, context: Context) -> Action {
+        self.controls.lock().unwrap().act(context)
+    }
+
+    fn name(&self) -> String {
+        "Human".to_string()
+    }
+
+    fn debug_overlay(&self) -> DebugOverlay {
+        self.controls.lock().unwrap().debug_overlay()
+    }
+
+    fn is_ready(&self) -> bool {
+        true
+    }
+
+    fn keeps_state(&self) -> bool {
+        // the engine hands the keys over to the instance created by the championship
+        true
+    }
+}
+
+impl HumanPlayer {
+    /// Whether the human has not chosen the next action yet. The turn is
+    /// played anyway once the waiting times out.
+    pub(crate) fn awaits_input(&self) -> bool {
+        self.controls.lock().unwrap().command.is_none()
+    }
+
+    /// Invoked with the keys pressed while the live viewer waits for the
+    /// human. Returns whether the key was used, the other keys controlling
+    /// the viewer as usual.
+    pub(crate) fn on_key(&self, key: KeyEvent) -> bool {
+        self.controls.lock().unwrap().on_key(key)
+    }
+}
+
+impl Controls {
+    fn act(&mut self, context: Context) -> Action {
+        let orientation = context.player_details().orientation;
+        let position = context.position().clone();
+
+        // the target is kept while aiming, unless the tank moved out of range
+        self.cursor = self
+            .cursor
+            .take()
+            .filter(|cursor| position.within_distance(cursor, POSITIONAL_SHOT_DISTANCE));
+        self.position = Some(position);
+        self.world_size = context.world_size().clone();
+
+        match self.command.take() {
+            Some(Command::Act(action)) => action,
+            Some(Command::FireAhead) => Action::Fire(Aiming::Cardinal(orientation)),
+            Some(Command::ScanAhead) => Action::Scan(ScanType::Mono(orientation)),
+            // no key was pressed in time
+            None => Action::Idle,
+        }
+    }
+
+    fn debug_overlay(&self) -> DebugOverlay {
+        let mut overlay = DebugOverlay::default();
+        if let (Some(cursor), Some(position)) = (&self.cursor, &self.position) {
+            overlay.draw_line(position.clone(), cursor.clone());
+        }
+        overlay
+    }
+
+    fn on_key(&mut self, key: KeyEvent) -> bool {
+        // while aiming, the arrows move the target instead of the view
+        if let Some(cursor) = self.cursor.clone() {
+            match key.code {
+                KeyCode::Up => self.move_cursor(Orientation::North),
+                KeyCode::Down => self.move_cursor(Orientation::South),
+                KeyCode::Left => self.move_cursor(Orientation::West),
+                KeyCode::Right => self.move_cursor(Orientation::East),
+                KeyCode::Enter if self.position.as_ref() != Some(&cursor) => {
+                    self.command = Some(Command::Act(Action::Fire(Aiming::Positional(cursor))));
+                    self.cursor = None;
+                }
+                KeyCode::Enter => {}
+                KeyCode::Char('t' | 'T') => self.cursor = None,
+                _ => return self.choose(key),
+            }
+            return true;
+        }
+
+        if let KeyCode::Char('t' | 'T') = key.code {
+            self.cursor = self.position.clone();
+            return self.cursor.is_some();
+        }
+
+        self.choose(key)
+    }
+}
+
+// Private functions
+impl Controls {
+    /// Picks the command bound to the key, if any
+    fn choose(&mut self, key: KeyEvent) -> bool {
+        let command = match key.code {
+            KeyCode::Char('w' | 'W') => Command::Act(Action::Move(Direction::Forward)),
+            KeyCode::Char('s' | 'S') => Command::Act(Action::Move(Direction::Backward)),
+            KeyCode::Char('a' | 'A') => Command::Act(Action::Rotate(Rotation::CounterClockwise)),
+            KeyCode::Char('d' | 'D') => Command::Act(Action::Rotate(Rotation::Clockwise)),
+            KeyCode::Char('q' | 'Q') => Command::Act(Action::Scan(ScanType::Omni)),
+            KeyCode::Char('e' | 'E') => Command::ScanAhead,
+            KeyCode::Char('x' | 'X') => Command::Act(Action::Idle),
+            KeyCode::Char(' ') => Command::FireAhead,
+            _ => return false,
+        };
+
+        self.command = Some(command);
+        self.cursor = None;
+        true
+    }
+
+    /// Moves the cursor by one cell, as long as the target stays in range
+    fn move_cursor(&mut self, heading: Orientation) {
+        let (Some(cursor), Some(position)) = (&self.cursor, &self.position) else {
+            return;
+        };
+
+        if let Some(next) = cursor
+            .follow(&heading, &self.world_size)
+            .filter(|next| position.within_distance(next, POSITIONAL_SHOT_DISTANCE))
+        {
+            self.cursor = Some(next);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{game::PlayerRng, player::Details};
+
+    fn context(position: Position, orientation: Orientation) -> Context {
+        let mut details = Details::new('🙂', 1);
+        details.orientation = orientation;
+        Context::new(
+            100,
+            Action::Idle,
+            details,
+            position,
+            PlayerRng::new(0),
+            None,
+            1,
+            WorldSize { x: 30, y: 30 },
+        )
+    }
+
+    fn press(player: &mut HumanPlayer, code: KeyCode) -> bool {
+        player.on_key(KeyEvent::from(code))
+    }
+
+    #[test]
+    fn test_keys_choose_the_next_action() {
+        let mut player = HumanPlayer::new();
+        assert!(player.awaits_input());
+        assert!(!press(&mut player, KeyCode::Up));
+
+        assert!(press(&mut player, KeyCode::Char('w')));
+        assert!(!player.awaits_input());
+        let origin = Position { x: 10, y: 10 };
+        assert_eq!(
+            Action::Move(Direction::Forward),
+            player.act(context(origin.clone(), Orientation::North))
+        );
+
+        // the shot is fired ahead of the tank when the turn is played
+        assert!(player.awaits_input());
+        press(&mut player, KeyCode::Char(' '));
+        assert_eq!(
+            Action::Fire(Aiming::Cardinal(Orientation::East)),
+            player.act(context(origin.clone(), Orientation::East))
+        );
+
+        // the turn is idle when no key was pressed in time
+        assert_eq!(Action::Idle, player.act(context(origin, Orientation::East)));
+    }
+
+    #[test]
+    fn test_cursor_aims_positional_shots_in_range() {
+        let mut player = HumanPlayer::new();
+        // nothing to aim from, before the first turn
+        assert!(!press(&mut player, KeyCode::Char('t')));
+
+        let origin = Position { x: 10, y: 10 };
+        player.act(context(origin.clone(), Orientation::North));
+        assert!(press(&mut player, KeyCode::Char('t')));
+        assert!(press(&mut player, KeyCode::Enter));
+        assert!(player.awaits_input());
+
+        for _ in 0..POSITIONAL_SHOT_DISTANCE + 2 {
+            press(&mut player, KeyCode::Right);
+        }
+        press(&mut player, KeyCode::Up);
+        let target = Position {
+            x: origin.x + POSITIONAL_SHOT_DISTANCE,
+            y: origin.y - 1,
+        };
+        assert_eq!(
+            DebugOverlay {
+                lines: vec![(origin.clone(), target.clone())],
+                ..Default::default()
+            },
+            player.debug_overlay()
+        );
+
+        assert!(press(&mut player, KeyCode::Enter));
+        assert!(player.debug_overlay().is_empty());
+        assert_eq!(
+            Action::Fire(Aiming::Positional(target)),
+            player.act(context(origin, Orientation::North))
+        );
+    }
+}
@@ -0,0 +1,94 @@
+//! Detailed view of a single tank, shown in the side panel of the live viewer
+
+use crate::engine::{human::KEYS_HELP, tank::Tank, theme::Theme};
+
+// The amount of log entries shown, and their maximum length
+const LOG_LINES: usize = 8;
+const LOG_LINE_LENGTH: usize = 60;
+
+/// Describes the state of the tank, its last scan and its last actions. The
+/// tank of a human player also shows the scanned cells, and the keys.
+pub fn inspect(tank: &Tank, theme: &Theme) -> Vec<String> {
+    let context = tank.context();
+    let details = context.player_details();
+
+    let mobility = if context.is_mobile() {
+        "mobile"
+    } else {
+        "immobile"
+    };
+    let cpu_time = context.cumulated_cpu_time() / 1000;
+    let cpu_time_per_turn = context.average_cpu_time_per_turn() / 1000;
+    let last_scan = match tank.last_scan() {
+        Some(scan) => format!(
+            "{} from {}, turn {} (highlighted)",
+            scan.scan_type, scan.from, scan.turn
+        ),
+        None => "none".to_string(),
+    };
+
+    let mut result = vec![
+        "[INSPECTOR]".to_string(),
+        "===========".to_string(),
+        format!(
+            "{} {} (id {:02})",
+            theme.avatar(details),
+            tank.player().name(),
+            details.id
+        ),
+        format!(
+            "Health:\t\t{:03}% [{}]",
+            context.health(),
+            tank.health_bar()
+        ),
+        format!("Mobility:\t{mobility}"),
+        format!("Score:\t\t{:02} pts", context.score()),
+        format!("Position:\t{}, {}", context.position(), details.orientation),
+        format!("CPU time:\t{cpu_time}us, {cpu_time_per_turn}us per turn"),
+        format!("Last action:\t{}", context.previous_action()),
+        format!("Last scan:\t{last_scan}"),
+        "History:".to_string(),
+    ];
+
+    // a human player gets to read its last scan, as a bot would
+    let human = tank.human().is_some();
+    if let Some(scan) = tank.last_scan().filter(|_| human) {
+        let grid = scan.data.iter().map(|row| {
+            let cells = row
+                .iter()
+                .map(|cell| theme.cell(cell, None))
+                .collect::<String>();
+            format!("  {cells}")
+        });
+        result.splice(
+            3..3,
+            std::iter::once("Scanned area:".to_string()).chain(grid),
+        );
+    }
+
+    for (turn, action) in tank.history().iter().rev() {
+        result.push(format!("  turn {turn:04}\t{action}"));
+    }
+
+    // while stepping back, the entries written after the displayed turn are hidden
+    let entries = tank.logger().last_entries(LOG_LINES, context.turn());
+    if !entries.is_empty() {
+        result.push("Log:".to_string());
+    }
+    for entry in entries {
+        let text = entry
+            .text
+            .chars()
+            .map(|c| if c.is_control() { ' ' } else { c })
+            .take(LOG_LINE_LENGTH)
+            .collect::<String>();
+        result.push(format!("  turn {:04}\t{text}", entry.turn));
+    }
+
+    if human {
+        result.push("Keys:".to_string());
+        result.extend(KEYS_HELP.iter().map(|keys| format!("  {keys}")));
+    }
+
+    result
+}
@@ -1,8 +1,29 @@
+mod championship;
 mod context;
+mod event;
+mod export;
+mod feed;
+mod game;
+mod human;
+mod inspector;
 mod outcome;
+mod rating;
+mod replay;
+mod report;
+mod rewind;
+mod seed;
 mod shell;
+mod stats;
 mod tank;
+mod theme;
+mod tournament;
+mod viewport;
 mod world;
 
-pub mod championship;
-pub mod game;
+pub use championship::{Championship, PlayerFactory, DEFAULT_STORAGE_DIRECTORY};
+pub use event::GameEvent;
+pub use outcome::{ChampionshipOutcome, GameOutcome};
+pub use rating::{RatingChange, Ratings, INITIAL_RATING};
+pub use stats::{ActionMix, PlayerStats};
+pub use theme::Theme;
+pub use tournament::{Format, MatchResult, Standing};
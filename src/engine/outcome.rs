@@ -1,44 +1,121 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
 
-use crate::api::{
-    action::Action, map_cell::MapCell, player::PlayerId, position::Position,
-    world_size::MAX_WORLD_SIZE,
+use crate::{
+    api::{
+        action::Action,
+        game::GameRules,
+        map_cell::{MapCell, Terrain, TreeType},
+        player::{PlayerId, PlayerIdentity},
+        position::Position,
+        world_size::{WorldSize, MAX_WORLD_SIZE},
+    },
+    engine::{
+        event::GameEvent,
+        rating::RatingChange,
+        stats::PlayerStats,
+        tournament::{Format, MatchResult, Standing},
+    },
 };
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct ChampionshipOutcome {
-    players: Vec<PlayerEntry>,
+    format: Format,
+    players: Vec<PlayerIdentity>,
     game_results: Vec<GameOutcome>,
+    matches: Vec<MatchResult>,
     ranks: HashMap<PlayerId, f32>,
+    rating_changes: HashMap<PlayerId, RatingChange>,
+    seed: u64,
+    standings: Vec<Standing>,
 }
 
 impl ChampionshipOutcome {
     pub fn new() -> Self {
         ChampionshipOutcome {
+            format: Format::default(),
             players: Vec::new(),
             game_results: Vec::new(),
+            matches: Vec::new(),
             ranks: HashMap::new(),
+            rating_changes: HashMap::new(),
+            seed: 0,
+            standings: Vec::new(),
         }
     }
 
-    pub fn register_player(&mut self, id: PlayerId, name: String) {
-        self.players.push(PlayerEntry { id, name });
+    /// Records the seed and the format the championship is played with
+    pub fn set_setup(&mut self, seed: u64, format: Format) {
+        self.seed = seed;
+        self.format = format;
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn format(&self) -> &Format {
+        &self.format
+    }
+
+    pub fn players(&self) -> &[PlayerIdentity] {
+        &self.players
+    }
+
+    pub fn game_results(&self) -> &[GameOutcome] {
+        &self.game_results
     }
 
+    pub fn register_player(&mut self, identity: PlayerIdentity) {
+        if self.players.iter().any(|entry| entry.id == identity.id) {
+            panic!("Player id {} is registered twice!", identity.id);
+        }
+        self.players.push(identity);
+    }
+
+    /// Adds the result of a game to the championship.
+    ///
+    /// Panics if the game roster or ranks do not match the registered identities,
+    /// since the ranks would otherwise be attributed to the wrong players.
     pub fn add_game_result(&mut self, game_result: GameOutcome) {
+        for identity in &game_result.roster {
+            if !self.players.contains(identity) {
+                panic!(
+                    "Game {} has player {identity} which does not match any registered identity!",
+                    game_result.game_id
+                );
+            }
+        }
+
+        for player_id in game_result.ranks.keys() {
+            if !game_result
+                .roster
+                .iter()
+                .any(|entry| entry.id == *player_id)
+            {
+                panic!(
+                    "Game {} has a rank for player id {player_id} which is not in its roster!",
+                    game_result.game_id
+                );
+            }
+        }
+
         self.game_results.push(game_result);
     }
 
+    /// Computes the average rank of every player, over the games it took part in
     pub fn compute_ranks(&mut self) {
+        let mut games: HashMap<PlayerId, u32> = HashMap::new();
+
         for game in &self.game_results {
             for (player_id, rank) in &game.ranks {
                 let computed_rank = self.ranks.entry(*player_id).or_default();
                 *computed_rank += *rank as f32;
+                *games.entry(*player_id).or_default() += 1;
             }
         }
 
-        for rank in self.ranks.values_mut() {
-            *rank /= self.game_results.len() as f32;
+        for (player_id, rank) in self.ranks.iter_mut() {
+            *rank /= games[player_id] as f32;
         }
     }
 
@@ -46,6 +123,65 @@ impl ChampionshipOutcome {
         self.ranks.clone()
     }
 
+    pub fn add_match_result(&mut self, result: MatchResult) {
+        self.matches.push(result);
+    }
+
+    pub fn get_matches(&self) -> &[MatchResult] {
+        &self.matches
+    }
+
+    pub fn set_standings(&mut self, standings: Vec<Standing>) {
+        self.standings = standings;
+    }
+
+    /// The final standings, according to the championship format
+    pub fn get_standings(&self) -> &[Standing] {
+        &self.standings
+    }
+
+    /// Counts the games won by every player against every other player, in the
+    /// games played by only two players. The key is (winner, loser).
+    pub fn head_to_head(&self) -> HashMap<(PlayerId, PlayerId), u32> {
+        let mut result = HashMap::new();
+
+        for game in &self.game_results {
+            if let [a, b] = game.roster.as_slice() {
+                let rank_a = game.ranks.get(&a.id).copied().unwrap_or(u8::MAX);
+                let rank_b = game.ranks.get(&b.id).copied().unwrap_or(u8::MAX);
+
+                if rank_a < rank_b {
+                    *result.entry((a.id, b.id)).or_default() += 1;
+                } else if rank_b < rank_a {
+                    *result.entry((b.id, a.id)).or_default() += 1;
+                }
+            }
+        }
+
+        result
+    }
+
+    pub fn add_rating_change(&mut self, id: PlayerId, change: RatingChange) {
+        self.rating_changes.insert(id, change);
+    }
+
+    pub fn get_rating_changes(&self) -> HashMap<PlayerId, RatingChange> {
+        self.rating_changes.clone()
+    }
+
+    /// Provides the statistics of every player, aggregated over all the games
+    pub fn get_stats(&self) -> HashMap<PlayerId, PlayerStats> {
+        let mut result: HashMap<PlayerId, PlayerStats> = HashMap::new();
+
+        for game in &self.game_results {
+            for (player_id, stats) in game.stats() {
+                result.entry(player_id).or_default().merge(&stats);
+            }
+        }
+
+        result
+    }
+
     pub fn get_player_name(&self, id: PlayerId) -> Option<String> {
         self.players
             .iter()
@@ -54,32 +190,103 @@ impl ChampionshipOutcome {
     }
 }
 
-#[derive(Debug)]
-pub struct PlayerEntry {
-    id: PlayerId,
-    name: String,
-}
-
 #[derive(Debug)]
 #[allow(dead_code)]
 pub struct GameOutcome {
     game_id: u32,
+    seed: u64,
+    world_size: WorldSize,
+    rules: GameRules,
     original_map: Box<[[MapCell; MAX_WORLD_SIZE]; MAX_WORLD_SIZE]>,
+    roster: Vec<PlayerIdentity>,
     turns: Vec<TurnOutcome>,
     ranks: HashMap<PlayerId, u8>,
+    scores: HashMap<PlayerId, u16>,
 }
 
 impl GameOutcome {
     pub fn new(
         game_id: u32,
+        seed: u64,
+        world_size: WorldSize,
+        rules: GameRules,
         original_map: Box<[[MapCell; MAX_WORLD_SIZE]; MAX_WORLD_SIZE]>,
+        roster: Vec<PlayerIdentity>,
     ) -> Self {
         GameOutcome {
             game_id,
+            seed,
+            world_size,
+            rules,
             original_map,
+            roster,
             turns: Vec::new(),
             ranks: HashMap::new(),
+            scores: HashMap::new(),
+        }
+    }
+
+    pub fn game_id(&self) -> u32 {
+        self.game_id
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn world_size(&self) -> &WorldSize {
+        &self.world_size
+    }
+
+    pub fn rules(&self) -> &GameRules {
+        &self.rules
+    }
+
+    /// The map as it was when the game started, with the players at their spawn positions
+    pub fn original_map(&self) -> &[[MapCell; MAX_WORLD_SIZE]; MAX_WORLD_SIZE] {
+        &self.original_map
+    }
+
+    pub fn roster(&self) -> &[PlayerIdentity] {
+        &self.roster
+    }
+
+    pub fn turn_count(&self) -> usize {
+        self.turns.len()
+    }
+
+    pub fn turns(&self) -> &[TurnOutcome] {
+        &self.turns
+    }
+
+    /// Identifies the terrain the game was played on, so that games played
+    /// on the same map can be told apart from the others
+    pub fn map_fingerprint(&self) -> u64 {
+        // FNV-1a, so that the result does not depend on the std hasher
+        let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+
+        for row in self.original_map.iter().take(self.world_size.y) {
+            for cell in row.iter().take(self.world_size.x) {
+                let terrain = match cell {
+                    MapCell::Explosion(_, terrain)
+                    | MapCell::Player(_, terrain)
+                    | MapCell::Shell(_, terrain)
+                    | MapCell::Terrain(terrain) => Some(*terrain),
+                    MapCell::Unallocated => None,
+                };
+                let code = match terrain {
+                    None => 0,
+                    Some(Terrain::Field) => 1,
+                    Some(Terrain::Lake) => 2,
+                    Some(Terrain::Forest(TreeType::Deciduous)) => 3,
+                    Some(Terrain::Forest(TreeType::Evergreen)) => 4,
+                    Some(Terrain::Swamp) => 5,
+                };
+                hash = (hash ^ code).wrapping_mul(0x0000_0100_0000_01b3);
+            }
         }
+
+        hash
     }
 
     pub fn add_turn_outcome(&mut self, turn: TurnOutcome) {
@@ -89,6 +296,74 @@ impl GameOutcome {
     pub fn add_player_rank(&mut self, id: PlayerId, rank: u8) {
         self.ranks.insert(id, rank);
     }
+
+    pub fn add_player_score(&mut self, id: PlayerId, score: u16) {
+        self.scores.insert(id, score);
+    }
+
+    pub fn ranks(&self) -> &HashMap<PlayerId, u8> {
+        &self.ranks
+    }
+
+    pub fn scores(&self) -> &HashMap<PlayerId, u16> {
+        &self.scores
+    }
+
+    /// Computes the statistics of every player taking part in the game
+    pub fn stats(&self) -> HashMap<PlayerId, PlayerStats> {
+        let mut result = self
+            .roster
+            .iter()
+            .map(|identity| {
+                let stats = PlayerStats {
+                    games: 1,
+                    ..Default::default()
+                };
+                (identity.id, stats)
+            })
+            .collect::<HashMap<_, _>>();
+
+        for turn in &self.turns {
+            for (player_id, outcome) in &turn.players {
+                let stats = result.entry(*player_id).or_default();
+                stats.turns_survived += 1;
+                stats.cpu_time += outcome.cpu_time;
+                stats.actions.add(&outcome.action);
+            }
+
+            for event in &turn.events {
+                match event {
+                    GameEvent::ShotFired { shooter_id, .. } => {
+                        result.entry(*shooter_id).or_default().shots_fired += 1;
+                    }
+                    GameEvent::ShellExploded { .. } | GameEvent::Trapped { .. } => {}
+                    GameEvent::Assist { player_id, .. } => {
+                        result.entry(*player_id).or_default().assists += 1;
+                    }
+                    GameEvent::Damage {
+                        player_id,
+                        amount,
+                        cause,
+                        fatal,
+                    } => {
+                        let shooter_id = result
+                            .entry(*player_id)
+                            .or_default()
+                            .add_damage(*player_id, *amount, cause, *fatal);
+
+                        if let Some(shooter_id) = shooter_id {
+                            result
+                                .entry(shooter_id)
+                                .or_default()
+                                .add_hit(*amount, cause, *fatal);
+                        }
+                    }
+                }
+            }
+        }
+
+        result
+    }
 }
 
 #[derive(Debug)]
@@ -96,6 +371,7 @@ impl GameOutcome {
 pub struct TurnOutcome {
     number: usize,
     players: HashMap<PlayerId, PlayerOutcome>,
+    events: Vec<GameEvent>,
 }
 
 impl TurnOutcome {
@@ -103,12 +379,30 @@ impl TurnOutcome {
         TurnOutcome {
             number,
             players: HashMap::new(),
+            events: Vec::new(),
         }
     }
 
+    pub fn add_events(&mut self, mut events: Vec<GameEvent>) {
+        self.events.append(&mut events);
+    }
+
     pub fn add_player_outcome(&mut self, id: PlayerId, outcome: PlayerOutcome) {
         self.players.insert(id, outcome);
     }
+
+    pub fn number(&self) -> usize {
+        self.number
+    }
+
+    /// The state of every live player at the start of the turn, along with its action
+    pub fn players(&self) -> &HashMap<PlayerId, PlayerOutcome> {
+        &self.players
+    }
+
+    pub fn events(&self) -> &[GameEvent] {
+        &self.events
+    }
 }
 
 #[derive(Debug)]
@@ -118,15 +412,217 @@ pub struct PlayerOutcome {
     health: u8,
     position: Position,
     score: u16,
+    cpu_time: Duration,
 }
 
 impl PlayerOutcome {
-    pub fn new(action: Action, health: u8, position: Position, score: u16) -> Self {
+    pub fn new(
+        action: Action,
+        health: u8,
+        position: Position,
+        score: u16,
+        cpu_time: Duration,
+    ) -> Self {
         PlayerOutcome {
             action: action.into(),
             health,
             position: position.into(),
             score,
+            cpu_time,
+        }
+    }
+
+    pub fn action(&self) -> &Action {
+        &self.action
+    }
+
+    pub fn health(&self) -> u8 {
+        self.health
+    }
+
+    pub fn position(&self) -> &Position {
+        &self.position
+    }
+
+    pub fn score(&self) -> u16 {
+        self.score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity(id: PlayerId, name: &str) -> PlayerIdentity {
+        PlayerIdentity {
+            id,
+            name: name.to_string(),
+            avatar: '🙂',
         }
     }
+
+    fn new_game(game_id: u32, roster: Vec<PlayerIdentity>) -> GameOutcome {
+        GameOutcome::new(
+            game_id,
+            0,
+            WorldSize { x: 2, y: 2 },
+            crate::engine::context::game_rules(10),
+            Box::new([[MapCell::Unallocated; MAX_WORLD_SIZE]; MAX_WORLD_SIZE]),
+            roster,
+        )
+    }
+
+    fn championship() -> ChampionshipOutcome {
+        let mut outcome = ChampionshipOutcome::new();
+        outcome.register_player(identity(1, "Alice"));
+        outcome.register_player(identity(2, "Bob"));
+        outcome
+    }
+
+    #[test]
+    fn test_ranks_are_keyed_by_identity() {
+        let mut outcome = championship();
+
+        // Bob is the only one who made it into the game
+        let mut game = new_game(1, vec![identity(2, "Bob")]);
+        game.add_player_rank(2, 1);
+        outcome.add_game_result(game);
+        outcome.compute_ranks();
+
+        assert_eq!(Some(&1.0), outcome.get_ranks().get(&2));
+        assert_eq!(None, outcome.get_ranks().get(&1));
+        assert_eq!(Some("Bob".to_string()), outcome.get_player_name(2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mismatched_roster_is_rejected() {
+        let mut outcome = championship();
+        let game = new_game(1, vec![identity(1, "Bob")]);
+        outcome.add_game_result(game);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rank_outside_roster_is_rejected() {
+        let mut outcome = championship();
+        let mut game = new_game(1, vec![identity(1, "Alice")]);
+        game.add_player_rank(2, 1);
+        outcome.add_game_result(game);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_duplicate_registration_is_rejected() {
+        let mut outcome = championship();
+        outcome.register_player(identity(2, "Carol"));
+    }
+
+    #[test]
+    fn test_stats_are_aggregated_over_games() {
+        use crate::api::{aiming::Aiming, game::DeathCause, orientation::Orientation};
+
+        let mut outcome = championship();
+        let roster = vec![identity(1, "Alice"), identity(2, "Bob")];
+
+        for game_id in 1..=2 {
+            let mut game = new_game(game_id, roster.clone());
+            let mut turn = TurnOutcome::new(1);
+            let fire = Action::Fire(Aiming::Cardinal(Orientation::East));
+            for player_id in [1, 2] {
+                let outcome = PlayerOutcome::new(
+                    fire.clone(),
+                    100,
+                    Position { x: 1, y: 1 },
+                    0,
+                    Duration::from_millis(2),
+                );
+                turn.add_player_outcome(player_id, outcome);
+            }
+            turn.add_events(vec![
+                GameEvent::ShotFired {
+                    shooter_id: 1,
+                    aim: Aiming::Cardinal(Orientation::East),
+                    from: Position { x: 1, y: 1 },
+                },
+                GameEvent::ShotFired {
+                    shooter_id: 2,
+                    aim: Aiming::Cardinal(Orientation::East),
+                    from: Position { x: 1, y: 1 },
+                },
+                GameEvent::Damage {
+                    player_id: 2,
+                    amount: 75,
+                    cause: DeathCause::DirectHit(1),
+                    fatal: false,
+                },
+                GameEvent::Damage {
+                    player_id: 2,
+                    amount: 25,
+                    cause: DeathCause::IndirectHit(1),
+                    fatal: true,
+                },
+            ]);
+            game.add_turn_outcome(turn);
+            game.add_player_rank(1, 1);
+            game.add_player_rank(2, 2);
+            outcome.add_game_result(game);
+        }
+
+        let stats = outcome.get_stats();
+        let alice = &stats[&1];
+        assert_eq!(2, alice.games);
+        assert_eq!(2, alice.shots_fired);
+        assert_eq!(2, alice.direct_hits);
+        assert_eq!(2, alice.indirect_hits);
+        assert_eq!(1.0, alice.accuracy());
+        assert_eq!(200, alice.damage_dealt);
+        assert_eq!(2, alice.kills);
+        assert_eq!(0, alice.deaths());
+        assert_eq!(Duration::from_millis(2), alice.cpu_time_per_turn());
+
+        let bob = &stats[&2];
+        assert_eq!(0.0, bob.accuracy());
+        assert_eq!(200, bob.damage_taken);
+        assert_eq!(2, bob.deaths_by_shell);
+        assert_eq!(2, bob.turns_survived);
+        assert_eq!(2, bob.actions.fire);
+    }
+
+    #[test]
+    fn test_head_to_head_counts_duels() {
+        let mut outcome = championship();
+        outcome.register_player(identity(3, "Carol"));
+        let duel = vec![identity(1, "Alice"), identity(2, "Bob")];
+
+        for (rank_alice, rank_bob) in [(1, 2), (2, 1), (1, 2), (1, 1)] {
+            let mut game = new_game(1, duel.clone());
+            game.add_player_rank(1, rank_alice);
+            game.add_player_rank(2, rank_bob);
+            outcome.add_game_result(game);
+        }
+
+        // games with more than two players are not duels
+        let mut game = new_game(
+            5,
+            vec![
+                identity(1, "Alice"),
+                identity(2, "Bob"),
+                identity(3, "Carol"),
+            ],
+        );
+        game.add_player_rank(1, 1);
+        game.add_player_rank(2, 2);
+        game.add_player_rank(3, 3);
+        outcome.add_game_result(game);
+
+        let head_to_head = outcome.head_to_head();
+        assert_eq!(Some(&2), head_to_head.get(&(1, 2)));
+        assert_eq!(Some(&1), head_to_head.get(&(2, 1)));
+        assert_eq!(None, head_to_head.get(&(1, 3)));
+
+        // ranks are averaged over the games played
+        outcome.compute_ranks();
+        assert_eq!(Some(&3.0), outcome.get_ranks().get(&3));
+    }
 }
@@ -0,0 +1,176 @@
+//! Skill ratings of the players, kept across championships.
+//!
+//! The ratings follow a multi-player Elo model: every game is considered as a
+//! set of duels between all the pairs of players taking part in it, where the
+//! better ranked player of each pair wins the duel.
+
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+/// The rating of a player who never played before
+pub const INITIAL_RATING: f64 = 1500.0;
+
+/// The maximum rating change of a player, for a single game
+const K_FACTOR: f64 = 32.0;
+
+/// The rating of a player before and after a championship
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RatingChange {
+    pub before: f64,
+    pub after: f64,
+}
+
+impl RatingChange {
+    pub fn delta(&self) -> f64 {
+        self.after - self.before
+    }
+}
+
+/// Ratings of the players, keyed by player name
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Ratings {
+    ratings: BTreeMap<String, f64>,
+}
+
+impl Ratings {
+    /// Loads the ratings from a file, having a `name<TAB>rating` line for every
+    /// player. A missing file holds no ratings.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let mut ratings = BTreeMap::new();
+
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            let rating = line
+                .rsplit_once('\t')
+                .and_then(|(name, rating)| Some((name, rating.trim().parse::<f64>().ok()?)));
+
+            match rating {
+                Some((name, rating)) => ratings.insert(name.to_string(), rating),
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("invalid rating line: {line}"),
+                    ))
+                }
+            };
+        }
+
+        Ok(Self { ratings })
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+
+        let text = self
+            .ratings
+            .iter()
+            .map(|(name, rating)| format!("{name}\t{rating}\n"))
+            .collect::<String>();
+        fs::write(path, text)
+    }
+
+    pub fn rating(&self, name: &str) -> f64 {
+        self.ratings.get(name).copied().unwrap_or(INITIAL_RATING)
+    }
+
+    /// Updates the ratings from the ranks of a game, where a lower rank is better
+    pub fn update(&mut self, ranks: &[(String, u8)]) {
+        if ranks.len() < 2 {
+            return;
+        }
+
+        let ratings = ranks
+            .iter()
+            .map(|(name, _)| self.rating(name))
+            .collect::<Vec<_>>();
+        let k = K_FACTOR / (ranks.len() - 1) as f64;
+
+        for (i, (name, rank)) in ranks.iter().enumerate() {
+            let mut delta = 0.0;
+
+            for (j, (_, other_rank)) in ranks.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+
+                let expected = 1.0 / (1.0 + 10f64.powf((ratings[j] - ratings[i]) / 400.0));
+                let actual = match rank.cmp(other_rank) {
+                    std::cmp::Ordering::Less => 1.0,
+                    std::cmp::Ordering::Equal => 0.5,
+                    std::cmp::Ordering::Greater => 0.0,
+                };
+                delta += k * (actual - expected);
+            }
+
+            *self.ratings.entry(name.clone()).or_insert(INITIAL_RATING) += delta;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranks(entries: &[(&str, u8)]) -> Vec<(String, u8)> {
+        entries
+            .iter()
+            .map(|(name, rank)| (name.to_string(), *rank))
+            .collect()
+    }
+
+    #[test]
+    fn test_winner_gains_what_loser_loses() {
+        let mut ratings = Ratings::default();
+        ratings.update(&ranks(&[("Alice", 1), ("Bob", 2)]));
+
+        assert_eq!(INITIAL_RATING + K_FACTOR / 2.0, ratings.rating("Alice"));
+        assert_eq!(INITIAL_RATING - K_FACTOR / 2.0, ratings.rating("Bob"));
+        assert_eq!(INITIAL_RATING, ratings.rating("Carol"));
+    }
+
+    #[test]
+    fn test_multi_player_game() {
+        let mut ratings = Ratings::default();
+        ratings.update(&ranks(&[
+            ("Alice", 2),
+            ("Bob", 1),
+            ("Carol", 3),
+            ("Dave", 2),
+        ]));
+
+        assert!(ratings.rating("Bob") > ratings.rating("Alice"));
+        assert_eq!(ratings.rating("Alice"), ratings.rating("Dave"));
+        assert!(ratings.rating("Alice") > ratings.rating("Carol"));
+
+        let total: f64 = ["Alice", "Bob", "Carol", "Dave"]
+            .iter()
+            .map(|name| ratings.rating(name))
+            .sum();
+        assert!((total - 4.0 * INITIAL_RATING).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_ratings_are_persisted() {
+        let path = std::env::temp_dir()
+            .join(format!("rbt-ratings-{}", std::process::id()))
+            .join("ratings.tsv");
+
+        assert_eq!(Ratings::default(), Ratings::load(&path).unwrap());
+
+        let mut ratings = Ratings::default();
+        ratings.update(&ranks(&[("Alice", 1), ("Bob the 2nd", 2)]));
+        ratings.save(&path).unwrap();
+
+        let loaded = Ratings::load(&path).unwrap();
+        assert_eq!(ratings.rating("Alice"), loaded.rating("Alice"));
+        assert_eq!(ratings.rating("Bob the 2nd"), loaded.rating("Bob the 2nd"));
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+}
@@ -0,0 +1,363 @@
+//! Self-contained HTML/JS replay of a single game, rendered on a canvas.
+//! The recorded turns are embedded as JSON, so the file opens offline.
+
+use crate::{
+    api::{
+        aiming::Aiming,
+        player::PlayerId,
+        position::{Position, CARDINAL_SHOT_DISTANCE},
+    },
+    engine::{
+        event::GameEvent,
+        export::{array, object, string},
+        outcome::{GameOutcome, TurnOutcome},
+        report::cell_colour,
+    },
+};
+
+const TEMPLATE: &str = r##"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Rusty Battle Tanks - game %GAME_ID%</title>
+<style>
+body { font-family: sans-serif; margin: 1.5em; color: #222; }
+.view { display: flex; gap: 1.5em; align-items: flex-start; }
+canvas { border: 1px solid #ccc; }
+table { border-collapse: collapse; }
+td, th { padding: 0.2em 0.5em; text-align: left; }
+.swatch { display: inline-block; width: 0.8em; height: 0.8em; }
+.dead { color: #999; }
+.controls { margin: 0.8em 0; display: flex; gap: 0.8em; align-items: center; }
+#turn { width: 40em; }
+</style>
+</head>
+<body>
+<h1>Game %GAME_ID%</h1>
+<div class="controls">
+<button id="play">Play</button>
+<input id="turn" type="range" min="0" value="0">
+<span id="label"></span>
+<select id="speed">
+<option value="400">Slow</option>
+<option value="100" selected>Normal</option>
+<option value="25">Fast</option>
+</select>
+</div>
+<div class="view">
+<canvas id="map"></canvas>
+<table id="players"></table>
+</div>
+<h2>Health</h2>
+<canvas id="timeline" width="960" height="160"></canvas>
+<script>
+const GAME = %GAME%;
+const CELL = Math.max(4, Math.floor(Math.min(960 / GAME.width, 720 / GAME.height)));
+const last = GAME.turns.length - 1;
+
+const map = document.getElementById("map");
+map.width = GAME.width * CELL;
+map.height = GAME.height * CELL;
+const ctx = map.getContext("2d");
+
+const terrain = document.createElement("canvas");
+terrain.width = map.width;
+terrain.height = map.height;
+const terrainCtx = terrain.getContext("2d");
+for (let y = 0; y < GAME.height; y++) {
+  for (let x = 0; x < GAME.width; x++) {
+    terrainCtx.fillStyle = GAME.palette[GAME.terrain.charCodeAt(y * GAME.width + x) - 48];
+    terrainCtx.fillRect(x * CELL, y * CELL, CELL, CELL);
+  }
+}
+
+const colours = GAME.players.map((_, i) => `hsl(${i * 360 / GAME.players.length}, 70%, 45%)`);
+const centre = (value) => value * CELL + CELL / 2;
+const scrubber = document.getElementById("turn");
+scrubber.max = last;
+let turn = 0;
+let timer = null;
+
+function drawMap() {
+  ctx.drawImage(terrain, 0, 0);
+  const current = GAME.turns[turn];
+
+  ctx.lineWidth = Math.max(1, CELL / 6);
+  for (const [shooter, fx, fy, tx, ty] of current.shots) {
+    ctx.strokeStyle = colours[GAME.players.findIndex((p) => p.id === shooter)];
+    ctx.beginPath();
+    ctx.moveTo(centre(fx), centre(fy));
+    ctx.lineTo(centre(tx), centre(ty));
+    ctx.stroke();
+    ctx.fillStyle = "rgba(255, 120, 0, 0.8)";
+    ctx.beginPath();
+    ctx.arc(centre(tx), centre(ty), CELL * 0.6, 0, 2 * Math.PI);
+    ctx.fill();
+  }
+
+  ctx.font = `${CELL}px sans-serif`;
+  ctx.textAlign = "center";
+  ctx.textBaseline = "middle";
+  for (const [id, x, y] of current.players) {
+    const player = GAME.players.find((p) => p.id === id);
+    ctx.fillText(player.avatar, centre(x), centre(y) + CELL / 10);
+  }
+}
+
+function drawPlayers() {
+  const current = GAME.turns[turn];
+  const rows = GAME.players.map((player, i) => {
+    const state = current.players.find(([id]) => id === player.id);
+    const [health, score, action] = state ? state.slice(3) : [0, "", ""];
+    return `<tr class="${state ? "" : "dead"}"><td><span class="swatch" style="background: ${colours[i]}"></span></td>` +
+      `<td>${player.avatar} ${escapeHtml(player.name)}</td><td>${health}</td><td>${score}</td><td>${action}</td></tr>`;
+  });
+  document.getElementById("players").innerHTML =
+    "<tr><th></th><th>Player</th><th>Health</th><th>Score</th><th>Action</th></tr>" + rows.join("");
+}
+
+function drawTimeline() {
+  const canvas = document.getElementById("timeline");
+  const tctx = canvas.getContext("2d");
+  const x = (index) => last > 0 ? index / last * (canvas.width - 1) : 0;
+  const y = (health) => canvas.height - 1 - health / 100 * (canvas.height - 2);
+  tctx.clearRect(0, 0, canvas.width, canvas.height);
+
+  GAME.players.forEach((player, i) => {
+    tctx.strokeStyle = colours[i];
+    tctx.lineWidth = 1.5;
+    tctx.beginPath();
+    GAME.turns.forEach((current, index) => {
+      const state = current.players.find(([id]) => id === player.id);
+      const health = state ? state[3] : 0;
+      if (index === 0) tctx.moveTo(x(index), y(health)); else tctx.lineTo(x(index), y(health));
+    });
+    tctx.stroke();
+  });
+
+  tctx.strokeStyle = "#000";
+  tctx.lineWidth = 1;
+  tctx.beginPath();
+  tctx.moveTo(x(turn), 0);
+  tctx.lineTo(x(turn), canvas.height);
+  tctx.stroke();
+}
+
+function escapeHtml(text) {
+  return text.replace(/&/g, "&amp;").replace(/</g, "&lt;").replace(/>/g, "&gt;");
+}
+
+function draw() {
+  if (last < 0) return;
+  scrubber.value = turn;
+  document.getElementById("label").textContent = `Turn ${GAME.turns[turn].number + 1} / ${last + 1}`;
+  drawMap();
+  drawPlayers();
+  drawTimeline();
+}
+
+function pause() {
+  clearInterval(timer);
+  timer = null;
+  document.getElementById("play").textContent = "Play";
+}
+
+function play() {
+  if (turn >= last) turn = 0;
+  document.getElementById("play").textContent = "Pause";
+  timer = setInterval(() => {
+    if (turn >= last) { pause(); return; }
+    turn++;
+    draw();
+  }, Number(document.getElementById("speed").value));
+}
+
+document.getElementById("play").onclick = () => timer ? pause() : play();
+document.getElementById("speed").onchange = () => { if (timer) { pause(); play(); } };
+scrubber.oninput = () => { turn = Number(scrubber.value); draw(); };
+draw();
+</script>
+</body>
+</html>
+"##;
+
+impl GameOutcome {
+    /// Renders the game as an HTML page, replaying it turn by turn
+    pub fn to_replay_html(&self) -> String {
+        TEMPLATE
+            .replace("%GAME_ID%", &self.game_id().to_string())
+            .replace("%GAME%", &self.replay_json())
+    }
+}
+
+// Private functions
+impl GameOutcome {
+    fn replay_json(&self) -> String {
+        let size = self.world_size();
+
+        // the terrain is encoded as one digit per cell, indexing the palette
+        let mut palette: Vec<&str> = Vec::new();
+        let mut terrain = String::with_capacity(size.x * size.y);
+        for row in self.original_map().iter().take(size.y) {
+            for cell in row.iter().take(size.x) {
+                let colour = cell_colour(cell);
+                let index = match palette.iter().position(|entry| *entry == colour) {
+                    Some(index) => index,
+                    None => {
+                        palette.push(colour);
+                        palette.len() - 1
+                    }
+                };
+                terrain.push(char::from(b'0' + index as u8));
+            }
+        }
+
+        let players = self.roster().iter().map(|identity| {
+            object(vec![
+                ("id", identity.id.to_string()),
+                ("name", string(&identity.name)),
+                ("avatar", string(&identity.avatar.to_string())),
+            ])
+        });
+
+        let turns = self.turns().iter().map(|turn| {
+            let mut player_ids = turn.players().keys().copied().collect::<Vec<_>>();
+            player_ids.sort();
+            let states = player_ids.iter().map(|player_id| {
+                let outcome = &turn.players()[player_id];
+                array([
+                    player_id.to_string(),
+                    outcome.position().x.to_string(),
+                    outcome.position().y.to_string(),
+                    outcome.health().to_string(),
+                    outcome.score().to_string(),
+                    string(&outcome.action().to_string()),
+                ])
+            });
+
+            let shots = self
+                .trajectories(turn)
+                .into_iter()
+                .map(|(shooter_id, from, to)| {
+                    array([
+                        shooter_id.to_string(),
+                        from.x.to_string(),
+                        from.y.to_string(),
+                        to.x.to_string(),
+                        to.y.to_string(),
+                    ])
+                });
+
+            object(vec![
+                ("number", turn.number().to_string()),
+                ("players", array(states)),
+                ("shots", array(shots)),
+            ])
+        });
+
+        object(vec![
+            ("width", size.x.to_string()),
+            ("height", size.y.to_string()),
+            ("palette", array(palette.into_iter().map(string))),
+            ("terrain", string(&terrain)),
+            ("players", array(players)),
+            ("turns", array(turns)),
+        ])
+    }
+
+    /// The shells fired during a turn, from their launch to their explosion.
+    /// Cardinal shells which never exploded flew off the map, while positional
+    /// shells which never exploded could not reach their target.
+    fn trajectories(&self, turn: &TurnOutcome) -> Vec<(PlayerId, Position, Position)> {
+        let mut result = Vec::new();
+
+        for event in turn.events() {
+            if let GameEvent::ShotFired {
+                shooter_id,
+                aim,
+                from,
+            } = event
+            {
+                let explosion = turn.events().iter().find_map(|event| match event {
+                    GameEvent::ShellExploded { shooter_id: id, at } if id == shooter_id => {
+                        Some(at.clone())
+                    }
+                    _ => None,
+                });
+
+                let to = match (explosion, aim) {
+                    (Some(at), _) => at,
+                    (None, Aiming::Cardinal(orientation)) => {
+                        let mut position = from.clone();
+                        for _ in 0..CARDINAL_SHOT_DISTANCE {
+                            match position.follow(orientation, self.world_size()) {
+                                Some(next) => position = next,
+                                None => break,
+                            }
+                        }
+                        position
+                    }
+                    (None, Aiming::Positional(_)) => continue,
+                };
+
+                result.push((*shooter_id, from.clone(), to));
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::{
+            action::Action,
+            map_cell::{MapCell, Terrain},
+            orientation::Orientation,
+            player::PlayerIdentity,
+            world_size::{WorldSize, MAX_WORLD_SIZE},
+        },
+        engine::{context::game_rules, outcome::PlayerOutcome},
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn test_replay_embeds_turns_and_trajectories() {
+        let alice = PlayerIdentity {
+            id: 1,
+            name: "</script>".to_string(),
+            avatar: '🙂',
+        };
+        let map = Box::new([[MapCell::Terrain(Terrain::Field); MAX_WORLD_SIZE]; MAX_WORLD_SIZE]);
+        let mut game = GameOutcome::new(
+            1,
+            7,
+            WorldSize { x: 4, y: 1 },
+            game_rules(10),
+            map,
+            vec![alice],
+        );
+
+        let fire = Action::Fire(Aiming::Cardinal(Orientation::East));
+        let mut turn = TurnOutcome::new(0);
+        turn.add_player_outcome(
+            1,
+            PlayerOutcome::new(fire, 100, Position { x: 0, y: 0 }, 0, Duration::ZERO),
+        );
+        turn.add_events(vec![GameEvent::ShotFired {
+            shooter_id: 1,
+            aim: Aiming::Cardinal(Orientation::East),
+            from: Position { x: 0, y: 0 },
+        }]);
+        game.add_turn_outcome(turn);
+
+        let html = game.to_replay_html();
+        // the shell flew off the map, so its trajectory ends on the border
+        assert!(html.contains(r#""shots":[[1,0,0,3,0]]"#));
+        assert!(html.contains(r#""terrain":"0000""#));
+        // the player names cannot close the script element
+        assert_eq!(1, html.matches("</script>").count());
+    }
+}
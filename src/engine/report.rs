@@ -0,0 +1,352 @@
+//! Self-contained HTML report of a championship, for sharing the results.
+//! Everything is inlined (styles and SVG drawings), so the file opens offline.
+
+use std::collections::HashMap;
+
+use crate::{
+    api::{
+        map_cell::{MapCell, Terrain, TreeType},
+        player::PlayerId,
+    },
+    engine::{
+        outcome::{ChampionshipOutcome, GameOutcome},
+        stats::PlayerStats,
+    },
+};
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; margin-bottom: 2em; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: right; }
+th { background: #eee; }
+td.name { text-align: left; }
+td.self { background: #ddd; }
+.games { display: flex; flex-wrap: wrap; gap: 1.5em; }
+.game { border: 1px solid #ccc; padding: 0.8em; }
+.game svg { display: block; margin-bottom: 0.5em; }
+.charts { display: flex; flex-wrap: wrap; gap: 2em; }
+.chart table { margin: 0; }
+.chart td, .chart th { border: none; padding: 0.1em 0.4em; }
+.bar { background: #4a7bd0; height: 0.9em; }
+";
+
+// A chart title, along with the value it shows for every player
+type Chart = (&'static str, fn(&PlayerStats) -> f64);
+
+// The size of every map cell in the thumbnails, in pixels
+const THUMBNAIL_CELL_SIZE: usize = 3;
+
+impl ChampionshipOutcome {
+    /// Renders the standings, the games, the statistics and the head-to-head
+    /// matrix as a single HTML page
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+
+        html += "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n";
+        html += "<title>Rusty Battle Tanks championship</title>\n";
+        html += &format!("<style>{STYLE}</style>\n</head>\n<body>\n");
+        html += "<h1>Rusty Battle Tanks championship</h1>\n";
+        html += &format!(
+            "<p>Format: {} &middot; Seed: {} &middot; Games: {}</p>\n",
+            escape(&self.format().to_string()),
+            self.seed(),
+            self.game_results().len()
+        );
+
+        html += &self.standings_html();
+        html += &self.stats_html();
+        html += &self.head_to_head_html();
+        html += &self.games_html();
+
+        html += "</body>\n</html>\n";
+        html
+    }
+}
+
+// Private functions
+impl ChampionshipOutcome {
+    fn standings_html(&self) -> String {
+        let rating_changes = self.get_rating_changes();
+        let mut html = String::from("<h2>Standings</h2>\n<table>\n");
+        html += "<tr><th>Rank</th><th>ID</th><th>Player</th><th>Points</th><th>Rating</th></tr>\n";
+
+        for standing in self.get_standings() {
+            let rating = rating_changes
+                .get(&standing.player_id)
+                .map(|change| format!("{:.0} ({:+.1})", change.after, change.delta()))
+                .unwrap_or_default();
+            html += &format!(
+                "<tr><td>{}</td><td>{}</td><td class=\"name\">{}</td><td>{:.2}</td><td>{}</td></tr>\n",
+                standing.position,
+                standing.player_id,
+                escape(&self.display_name(standing.player_id)),
+                standing.points,
+                rating
+            );
+        }
+
+        html + "</table>\n"
+    }
+
+    fn stats_html(&self) -> String {
+        let stats = self.get_stats();
+        let mut player_ids = stats.keys().copied().collect::<Vec<_>>();
+        player_ids.sort();
+
+        let charts: [Chart; 9] = [
+            ("Shots fired", |s| s.shots_fired as f64),
+            ("Accuracy (%)", |s| s.accuracy() as f64 * 100.0),
+            ("Damage dealt", |s| s.damage_dealt as f64),
+            ("Damage taken", |s| s.damage_taken as f64),
+            ("Kills", |s| s.kills as f64),
+            ("Assists", |s| s.assists as f64),
+            ("Deaths", |s| s.deaths() as f64),
+            ("Turns survived", |s| s.turns_survived as f64),
+            ("CPU time per turn (us)", |s| {
+                s.cpu_time_per_turn().as_micros() as f64
+            }),
+        ];
+
+        let mut html = String::from("<h2>Statistics</h2>\n<div class=\"charts\">\n");
+        for (title, value) in charts {
+            let values = player_ids
+                .iter()
+                .map(|id| (*id, value(&stats[id])))
+                .collect::<Vec<_>>();
+            html += &self.bar_chart(title, &values);
+        }
+
+        html + "</div>\n"
+    }
+
+    fn bar_chart(&self, title: &str, values: &[(PlayerId, f64)]) -> String {
+        let max = values
+            .iter()
+            .map(|(_, value)| *value)
+            .fold(0.0_f64, f64::max);
+
+        let mut html = format!("<div class=\"chart\"><h3>{}</h3>\n<table>\n", escape(title));
+        for (player_id, value) in values {
+            let width = if max > 0.0 { value / max * 200.0 } else { 0.0 };
+            html += &format!(
+                "<tr><td class=\"name\">{}</td><td>{:.1}</td>\
+                 <td class=\"name\"><div class=\"bar\" style=\"width: {:.0}px\"></div></td></tr>\n",
+                escape(&self.display_name(*player_id)),
+                value,
+                width
+            );
+        }
+
+        html + "</table></div>\n"
+    }
+
+    fn head_to_head_html(&self) -> String {
+        let head_to_head = self.head_to_head();
+        if head_to_head.is_empty() {
+            return String::new();
+        }
+
+        let player_ids = self
+            .get_standings()
+            .iter()
+            .map(|standing| standing.player_id)
+            .collect::<Vec<_>>();
+
+        let mut html = String::from("<h2>Head to head</h2>\n");
+        html += "<p>Games won by the player of the row, against the player of the column</p>\n";
+        html += "<table>\n<tr><th></th>";
+        for id in &player_ids {
+            html += &format!("<th>{id}</th>");
+        }
+        html += "</tr>\n";
+
+        for a in &player_ids {
+            html += &format!(
+                "<tr><td class=\"name\">{a} {}</td>",
+                escape(&self.display_name(*a))
+            );
+            for b in &player_ids {
+                if a == b {
+                    html += "<td class=\"self\">-</td>";
+                } else {
+                    let wins = head_to_head.get(&(*a, *b)).copied().unwrap_or_default();
+                    html += &format!("<td>{wins}</td>");
+                }
+            }
+            html += "</tr>\n";
+        }
+
+        html + "</table>\n"
+    }
+
+    fn games_html(&self) -> String {
+        let mut html = String::from("<h2>Games</h2>\n<div class=\"games\">\n");
+
+        for game in self.game_results() {
+            html += &format!("<div class=\"game\"><h3>Game {}</h3>\n", game.game_id());
+            html += &thumbnail(game);
+            html += &format!(
+                "<p>Seed {} &middot; {} turns &middot; <a href=\"replays/game_{}.html\">replay</a></p>\n",
+                game.seed(),
+                game.turn_count(),
+                game.game_id()
+            );
+
+            let mut roster = game.roster().to_vec();
+            roster.sort_by_key(|identity| {
+                (
+                    game.ranks().get(&identity.id).copied().unwrap_or(u8::MAX),
+                    identity.id,
+                )
+            });
+
+            html += "<table>\n<tr><th>Rank</th><th>Player</th><th>Score</th></tr>\n";
+            for identity in roster {
+                html += &format!(
+                    "<tr><td>{}</td><td class=\"name\">{} {}</td><td>{}</td></tr>\n",
+                    optional(game.ranks().get(&identity.id)),
+                    identity.avatar,
+                    escape(&identity.name),
+                    optional(game.scores().get(&identity.id))
+                );
+            }
+            html += "</table></div>\n";
+        }
+
+        html + "</div>\n"
+    }
+
+    fn display_name(&self, player_id: PlayerId) -> String {
+        self.players()
+            .iter()
+            .find(|identity| identity.id == player_id)
+            .map(|identity| format!("{} {}", identity.avatar, identity.name))
+            .unwrap_or_default()
+    }
+}
+
+/// Draws the original map of a game as an SVG picture, with the players at
+/// their spawn positions
+fn thumbnail(game: &GameOutcome) -> String {
+    let size = game.world_size();
+    let map = game.original_map();
+    let names = game
+        .roster()
+        .iter()
+        .map(|identity| (identity.id, identity.name.as_str()))
+        .collect::<HashMap<_, _>>();
+
+    let mut svg = format!(
+        "<svg width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" shape-rendering=\"crispEdges\">\n",
+        size.x * THUMBNAIL_CELL_SIZE,
+        size.y * THUMBNAIL_CELL_SIZE,
+        size.x,
+        size.y
+    );
+    let mut players = String::new();
+
+    for (y, row) in map.iter().take(size.y).enumerate() {
+        // adjacent cells of the same colour are drawn as a single rectangle
+        let mut x = 0;
+        while x < size.x {
+            let colour = cell_colour(&row[x]);
+            let start = x;
+            while x < size.x && cell_colour(&row[x]) == colour {
+                x += 1;
+            }
+            svg += &format!(
+                "<rect x=\"{start}\" y=\"{y}\" width=\"{}\" height=\"1\" fill=\"{colour}\"/>\n",
+                x - start
+            );
+        }
+
+        for (x, cell) in row.iter().take(size.x).enumerate() {
+            if let MapCell::Player(details, _) = cell {
+                let name = names.get(&details.id).copied().unwrap_or_default();
+                players += &format!(
+                    "<circle cx=\"{}.5\" cy=\"{}.5\" r=\"1.5\" fill=\"#e53935\" stroke=\"#fff\" stroke-width=\"0.3\"><title>{}</title></circle>\n",
+                    x,
+                    y,
+                    escape(name)
+                );
+            }
+        }
+    }
+
+    svg + &players + "</svg>\n"
+}
+
+pub(super) fn cell_colour(cell: &MapCell) -> &'static str {
+    let terrain = match cell {
+        MapCell::Explosion(_, terrain)
+        | MapCell::Player(_, terrain)
+        | MapCell::Shell(_, terrain)
+        | MapCell::Terrain(terrain) => terrain,
+        MapCell::Unallocated => return "#000000",
+    };
+
+    match terrain {
+        Terrain::Field => "#7cc35a",
+        Terrain::Lake => "#3d7fd9",
+        Terrain::Forest(TreeType::Deciduous) => "#2e7d32",
+        Terrain::Forest(TreeType::Evergreen) => "#1b5e20",
+        Terrain::Swamp => "#8d6e63",
+    }
+}
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map_or("-".to_string(), |value| value.to_string())
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::{
+            player::{Details, PlayerIdentity},
+            world_size::{WorldSize, MAX_WORLD_SIZE},
+        },
+        engine::context::game_rules,
+    };
+
+    #[test]
+    fn test_report_is_self_contained() {
+        let alice = PlayerIdentity {
+            id: 1,
+            name: "<Alice>".to_string(),
+            avatar: '🙂',
+        };
+        let mut outcome = ChampionshipOutcome::new();
+        outcome.register_player(alice.clone());
+
+        let mut map =
+            Box::new([[MapCell::Terrain(Terrain::Field); MAX_WORLD_SIZE]; MAX_WORLD_SIZE]);
+        map[0][2] = MapCell::Terrain(Terrain::Lake);
+        map[1][1] = MapCell::Player(Details::new('🙂', 1), Terrain::Field);
+        let mut game = GameOutcome::new(
+            1,
+            7,
+            WorldSize { x: 3, y: 2 },
+            game_rules(10),
+            map,
+            vec![alice],
+        );
+        game.add_player_rank(1, 1);
+        outcome.add_game_result(game);
+
+        let html = outcome.to_html();
+        assert!(!html.contains("<Alice>"));
+        assert!(html.contains("&lt;Alice&gt;"));
+        assert!(!html.contains("http"));
+        // the fields of the first row are merged into a single rectangle
+        assert!(html.contains("<rect x=\"0\" y=\"0\" width=\"2\" height=\"1\" fill=\"#7cc35a\"/>"));
+        assert!(html.contains("<circle cx=\"1.5\" cy=\"1.5\""));
+    }
+}
@@ -0,0 +1,146 @@
+//! History of the last states of a game, for stepping back and forth in the
+//! live viewer. Only the displayed state is rewound, never the players.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::{
+    api::{map_cell::MapCell, player::PlayerId, world_size::MAX_WORLD_SIZE},
+    engine::tank::{Tank, TankState},
+};
+
+/// The state of the world displayed after a turn
+#[derive(Clone)]
+pub struct WorldSnapshot {
+    pub map: Box<[[MapCell; MAX_WORLD_SIZE]; MAX_WORLD_SIZE]>,
+    pub tanks: HashMap<PlayerId, TankState>,
+    pub turn_number: usize,
+}
+
+impl WorldSnapshot {
+    pub fn capture(
+        map: &[[MapCell; MAX_WORLD_SIZE]; MAX_WORLD_SIZE],
+        tanks: &HashMap<PlayerId, Tank>,
+        turn_number: usize,
+    ) -> Self {
+        Self {
+            map: Box::new(*map),
+            tanks: tanks
+                .iter()
+                .map(|(player_id, tank)| (*player_id, tank.state()))
+                .collect(),
+            turn_number,
+        }
+    }
+}
+
+/// A ring buffer of the last `capacity` recorded states, the last one being the
+/// live state. While stepping back, the live state is kept aside, so that it
+/// can be restored once the viewer is back to the present.
+pub struct Rewind<T> {
+    capacity: usize,
+    live: Option<T>,
+    snapshots: VecDeque<T>,
+    viewed: Option<usize>,
+}
+
+impl<T: Clone> Rewind<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            live: None,
+            snapshots: VecDeque::new(),
+            viewed: None,
+        }
+    }
+
+    /// Records the live state, after a turn. Panics while stepping back, since
+    /// the game is not supposed to go on meanwhile.
+    pub fn record(&mut self, snapshot: T) {
+        if self.viewed.is_some() {
+            panic!("The game went on while viewing a past turn!");
+        }
+
+        if self.snapshots.len() == self.capacity {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(snapshot);
+    }
+
+    /// Provides the state preceding the viewed one, if it is still recorded.
+    /// When leaving the present, the `live` state is kept aside.
+    pub fn back(&mut self, live: impl FnOnce() -> T) -> Option<T> {
+        let target = match self.viewed {
+            None => self.snapshots.len().checked_sub(2)?,
+            Some(index) => index.checked_sub(1)?,
+        };
+
+        if self.viewed.is_none() {
+            self.live = Some(live());
+        }
+        self.viewed = Some(target);
+
+        Some(self.snapshots[target].clone())
+    }
+
+    /// Provides the state following the viewed one, which is the live state
+    /// when reaching the present
+    pub fn forward(&mut self) -> Option<T> {
+        let index = self.viewed?;
+
+        if index + 2 < self.snapshots.len() {
+            self.viewed = Some(index + 1);
+            Some(self.snapshots[index + 1].clone())
+        } else {
+            self.present()
+        }
+    }
+
+    /// Provides the live state, when viewing a past one
+    pub fn present(&mut self) -> Option<T> {
+        self.viewed.take()?;
+        self.live.take()
+    }
+
+    /// The amount of turns between the viewed state and the live one
+    pub fn turns_back(&self) -> Option<usize> {
+        self.viewed.map(|index| self.snapshots.len() - 1 - index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steps_back_and_forth() {
+        let mut rewind = Rewind::new(3);
+        for turn in 1..=4 {
+            rewind.record(turn);
+        }
+
+        // the live state is the last recorded one, and only 3 states are kept
+        assert_eq!(Some(3), rewind.back(|| 4));
+        assert_eq!(Some(1), rewind.turns_back());
+        assert_eq!(Some(2), rewind.back(|| unreachable!()));
+        assert_eq!(None, rewind.back(|| unreachable!()));
+        assert_eq!(Some(2), rewind.turns_back());
+
+        assert_eq!(Some(3), rewind.forward());
+        assert_eq!(Some(4), rewind.forward());
+        assert_eq!(None, rewind.turns_back());
+        assert_eq!(None, rewind.forward());
+    }
+
+    #[test]
+    fn test_present_restores_the_live_state() {
+        let mut rewind = Rewind::new(10);
+        assert_eq!(None, rewind.back(|| 0));
+
+        rewind.record(1);
+        rewind.record(2);
+        assert_eq!(Some(1), rewind.back(|| 2));
+        assert_eq!(Some(2), rewind.present());
+        assert_eq!(None, rewind.present());
+        rewind.record(3);
+    }
+}
@@ -0,0 +1,65 @@
+//! Derivation of the seeds used by the random number generators of the engine
+//! and of the players. The same championship seed always yields the same games.
+
+use crate::api::player::PlayerIdentity;
+
+/// Derives the seed of a game, from the seed of the championship
+pub fn game_seed(championship_seed: u64, game_id: u32) -> u64 {
+    mix(championship_seed ^ mix(game_id as u64))
+}
+
+/// Derives the seed of a player's RNG, from the seed of the game and the
+/// identity of the player
+pub fn player_seed(game_seed: u64, identity: &PlayerIdentity) -> u64 {
+    // FNV-1a, so that the result does not depend on the std hasher
+    let name_hash = identity
+        .name
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+
+    mix(game_seed ^ mix(identity.id as u64) ^ name_hash)
+}
+
+// SplitMix64 finalizer
+fn mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity(id: u8, name: &str) -> PlayerIdentity {
+        PlayerIdentity {
+            id,
+            name: name.to_string(),
+            avatar: '🙂',
+        }
+    }
+
+    #[test]
+    fn test_seeds_are_reproducible() {
+        assert_eq!(game_seed(42, 1), game_seed(42, 1));
+        assert_ne!(game_seed(42, 1), game_seed(42, 2));
+        assert_ne!(game_seed(42, 1), game_seed(43, 1));
+
+        let seed = game_seed(42, 1);
+        assert_eq!(
+            player_seed(seed, &identity(1, "Tantti")),
+            player_seed(seed, &identity(1, "Tantti"))
+        );
+        assert_ne!(
+            player_seed(seed, &identity(1, "Tantti")),
+            player_seed(seed, &identity(2, "Tantti"))
+        );
+        assert_ne!(
+            player_seed(seed, &identity(1, "Tantti")),
+            player_seed(seed, &identity(1, "Luis"))
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use crate::api::{
     aiming::Aiming,
+    player::PlayerId,
     position::{Position, CARDINAL_SHOT_DISTANCE, POSITIONAL_SHOT_DISTANCE},
     world_size::WorldSize,
 };
@@ -18,22 +19,25 @@ pub enum ShellState {
 pub struct Shell {
     current_pos: Option<Position>,
     fired_from: Position,
+    shooter_id: PlayerId,
     aim_type: Aiming,
     state: ShellState,
 }
 
 impl Shell {
-    pub fn new(aim_type: Aiming, fired_from: Position) -> Self {
+    pub fn new(shooter_id: PlayerId, aim_type: Aiming, fired_from: Position) -> Self {
         Self {
             current_pos: Some(fired_from.clone()),
             fired_from,
+            shooter_id,
             aim_type,
             state: ShellState::NotLaunched,
         }
     }
 
-    pub fn fired_from(&self) -> Position {
-        self.fired_from.clone()
+    /// The player who fired the shell, and is credited for its hits
+    pub fn shooter_id(&self) -> PlayerId {
+        self.shooter_id
     }
 
     pub fn pos(&self) -> Option<Position> {
@@ -0,0 +1,146 @@
+//! Statistics gathered for every player, over all the games of a championship
+
+use std::time::Duration;
+
+use crate::api::{action::Action, game::DeathCause, player::PlayerId};
+
+/// How many times each kind of action was chosen
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ActionMix {
+    pub idle: u32,
+    pub fire: u32,
+    pub moves: u32,
+    pub rotate: u32,
+    pub scan: u32,
+}
+
+impl ActionMix {
+    pub fn add(&mut self, action: &Action) {
+        match action {
+            Action::Idle => self.idle += 1,
+            Action::Fire(_) => self.fire += 1,
+            Action::Move(_) => self.moves += 1,
+            Action::Rotate(_) => self.rotate += 1,
+            Action::Scan(_) => self.scan += 1,
+        }
+    }
+
+    pub fn total(&self) -> u32 {
+        self.idle + self.fire + self.moves + self.rotate + self.scan
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.idle += other.idle;
+        self.fire += other.fire;
+        self.moves += other.moves;
+        self.rotate += other.rotate;
+        self.scan += other.scan;
+    }
+}
+
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PlayerStats {
+    pub games: u32,
+    pub shots_fired: u32,
+    /// Other players directly hit by the player's shells
+    pub direct_hits: u32,
+    /// Other players indirectly hit by the player's shells
+    pub indirect_hits: u32,
+    /// Health taken by the player's shells from the other players
+    pub damage_dealt: u32,
+    /// Health lost by the player, whatever the cause
+    pub damage_taken: u32,
+    /// Other players killed by the player's shells
+    pub kills: u32,
+    /// Other players hit by the player's shells, and later killed by another player
+    pub assists: u32,
+    pub deaths_by_shell: u32,
+    pub deaths_by_lake: u32,
+    pub deaths_by_collision: u32,
+    pub turns_survived: u32,
+    pub cpu_time: Duration,
+    pub actions: ActionMix,
+}
+
+impl PlayerStats {
+    /// The share of the shots directly hitting another player
+    pub fn accuracy(&self) -> f32 {
+        if self.shots_fired > 0 {
+            self.direct_hits as f32 / self.shots_fired as f32
+        } else {
+            0.0
+        }
+    }
+
+    pub fn deaths(&self) -> u32 {
+        self.deaths_by_shell + self.deaths_by_lake + self.deaths_by_collision
+    }
+
+    pub fn cpu_time_per_turn(&self) -> Duration {
+        let turns = self.actions.total();
+        if turns > 0 {
+            self.cpu_time / turns
+        } else {
+            Duration::ZERO
+        }
+    }
+
+    pub fn merge(&mut self, other: &Self) {
+        self.games += other.games;
+        self.shots_fired += other.shots_fired;
+        self.direct_hits += other.direct_hits;
+        self.indirect_hits += other.indirect_hits;
+        self.damage_dealt += other.damage_dealt;
+        self.damage_taken += other.damage_taken;
+        self.kills += other.kills;
+        self.assists += other.assists;
+        self.deaths_by_shell += other.deaths_by_shell;
+        self.deaths_by_lake += other.deaths_by_lake;
+        self.deaths_by_collision += other.deaths_by_collision;
+        self.turns_survived += other.turns_survived;
+        self.cpu_time += other.cpu_time;
+        self.actions.merge(&other.actions);
+    }
+
+    /// Accounts for `amount` of damage taken by `player_id`. Returns the shooter
+    /// to be credited, if the damage was dealt by another player's shell.
+    pub(crate) fn add_damage(
+        &mut self,
+        player_id: PlayerId,
+        amount: u8,
+        cause: &DeathCause,
+        fatal: bool,
+    ) -> Option<PlayerId> {
+        self.damage_taken += amount as u32;
+
+        if fatal {
+            match cause {
+                DeathCause::DirectHit(_) | DeathCause::IndirectHit(_) => self.deaths_by_shell += 1,
+                DeathCause::Drowning => self.deaths_by_lake += 1,
+                DeathCause::CollisionWithPlayer(_) | DeathCause::CollisionWithForest => {
+                    self.deaths_by_collision += 1
+                }
+            }
+        }
+
+        match cause {
+            DeathCause::DirectHit(shooter_id) | DeathCause::IndirectHit(shooter_id)
+                if *shooter_id != player_id =>
+            {
+                Some(*shooter_id)
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn add_hit(&mut self, amount: u8, cause: &DeathCause, fatal: bool) {
+        self.damage_dealt += amount as u32;
+        match cause {
+            DeathCause::DirectHit(_) => self.direct_hits += 1,
+            _ => self.indirect_hits += 1,
+        }
+        if fatal {
+            self.kills += 1;
+        }
+    }
+}
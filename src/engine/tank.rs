@@ -1,13 +1,72 @@
-use crate::{api::player::Player, engine::context::Context};
+use std::collections::VecDeque;
+
+use crate::{
+    api::{
+        action::Action,
+        game::PlayerRng,
+        log::Logger,
+        map_cell::MapCell,
+        player::{Avatar, Player},
+        position::{Position, SCANNING_DISTANCE},
+        scan::ScanType,
+    },
+    engine::{context::Context, human::HumanPlayer},
+};
+
+// The amount of past actions kept for the inspector
+const ACTION_HISTORY_LENGTH: usize = 8;
+
+/// The last scan performed by a tank
+#[derive(Clone, Debug)]
+pub struct LastScan {
+    pub turn: usize,
+    pub scan_type: ScanType,
+    pub from: Position,
+    pub data: Box<[[MapCell; SCANNING_DISTANCE]; SCANNING_DISTANCE]>,
+}
+
+/// The displayed state of a tank, kept to view past turns again
+#[derive(Clone)]
+pub struct TankState {
+    context: Context,
+    history: VecDeque<(usize, Action)>,
+    last_scan: Option<LastScan>,
+}
 
 pub struct Tank {
+    avatar: Avatar,
     context: Context,
+    death_notified: bool,
+    history: VecDeque<(usize, Action)>,
+    human: Option<HumanPlayer>,
+    last_scan: Option<LastScan>,
+    logger: Logger,
     player: Box<dyn Player>,
+    rng: PlayerRng,
 }
 
 impl Tank {
-    pub fn new(player: Box<dyn Player>, context: Context) -> Self {
-        Self { context, player }
+    pub fn new(player: Box<dyn Player>, context: Context, rng: PlayerRng) -> Self {
+        Self {
+            avatar: context.player_details().avatar,
+            context,
+            death_notified: false,
+            history: VecDeque::new(),
+            human: None,
+            last_scan: None,
+            logger: Logger::default(),
+            player,
+            rng,
+        }
+    }
+
+    pub fn into_player(self) -> Box<dyn Player> {
+        self.player
+    }
+
+    /// The avatar of the player, which the tank loses when it dies
+    pub fn avatar(&self) -> Avatar {
+        self.avatar
     }
 
     pub fn context(&self) -> &Context {
@@ -18,6 +77,16 @@ impl Tank {
         &mut self.context
     }
 
+    /// The log of the player, shared with it when the game starts
+    pub fn logger(&self) -> &Logger {
+        &self.logger
+    }
+
+    /// The random number generator of the player, shared with it in every turn
+    pub fn rng(&self) -> &PlayerRng {
+        &self.rng
+    }
+
     pub fn player(&self) -> &Box<dyn Player> {
         &self.player
     }
@@ -26,8 +95,15 @@ impl Tank {
         &mut self.player
     }
 
-    pub fn set_context(&mut self, context: Context) {
-        self.context = context;
+    /// The player of the tank, when it is controlled from the keyboard
+    pub fn human(&self) -> Option<&HumanPlayer> {
+        self.human.as_ref()
+    }
+
+    /// Marks the tank as controlled from the keyboard, through the given
+    /// handle of its player
+    pub fn set_human(&mut self, human: HumanPlayer) {
+        self.human = Some(human);
     }
 
     pub fn health_bar(&self) -> String {
@@ -44,7 +120,55 @@ impl Tank {
         bar
     }
 
+    /// Keeps the action chosen during the given turn, forgetting the oldest ones
+    pub fn record_action(&mut self, turn: usize, action: Action) {
+        if self.history.len() == ACTION_HISTORY_LENGTH {
+            self.history.pop_front();
+        }
+        self.history.push_back((turn, action));
+    }
+
+    /// The last actions, along with the turns during which they were chosen
+    pub fn history(&self) -> &VecDeque<(usize, Action)> {
+        &self.history
+    }
+
+    pub fn record_scan(&mut self, scan: LastScan) {
+        self.last_scan = Some(scan);
+    }
+
+    pub fn last_scan(&self) -> Option<&LastScan> {
+        self.last_scan.as_ref()
+    }
+
+    pub fn state(&self) -> TankState {
+        TankState {
+            context: self.context.clone(),
+            history: self.history.clone(),
+            last_scan: self.last_scan.clone(),
+        }
+    }
+
+    /// Replaces the displayed state of the tank. The player is left untouched.
+    pub fn restore(&mut self, state: TankState) {
+        self.context = state.context;
+        self.history = state.history;
+        self.last_scan = state.last_scan;
+    }
+
     pub fn survivor_bonus(&mut self) {
         self.context.reward_survivor();
     }
+
+    /// Lets the player know about its death, only once
+    pub fn notify_death(&mut self) {
+        if self.death_notified {
+            return;
+        }
+
+        if let Some(cause) = self.context.death_cause() {
+            self.death_notified = true;
+            self.player.on_death(cause);
+        }
+    }
 }
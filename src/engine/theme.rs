@@ -0,0 +1,238 @@
+//! Rendering of the map cells in the live viewer. Besides the emoji, a plain
+//! ASCII theme is available for the terminals which do not render the emoji
+//! with a consistent width. Every cell is two columns wide in both themes.
+
+use crate::{
+    api::{
+        map_cell::{MapCell, Terrain, TreeType},
+        orientation::Orientation,
+        player::{Avatar, Details, PlayerId},
+    },
+    frame::CELL_WIDTH,
+};
+
+const RESET: &str = "\x1b[0m";
+
+// The 256-colour palette entries of the tanks, picked by player id
+const PLAYER_COLOURS: [u8; 12] = [196, 226, 51, 201, 208, 231, 129, 46, 214, 39, 163, 118];
+const DEAD_PLAYER_COLOUR: u8 = 244;
+
+/// The background of a highlighted cell
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Highlight {
+    /// Covered by the last scan of the inspected tank
+    Scan,
+    /// Marked by the debug overlay of the inspected tank
+    Overlay,
+    /// Believed by the inspected tank to be another terrain
+    Drift,
+}
+
+impl Highlight {
+    fn colour(&self) -> u8 {
+        match self {
+            Self::Scan => 220,
+            Self::Overlay => 201,
+            Self::Drift => 160,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Theme {
+    #[default]
+    Emoji,
+    /// Letters for the tanks and coloured blocks for the terrain
+    Ascii,
+}
+
+impl Theme {
+    /// Renders a map cell, optionally on a highlighted background
+    pub fn cell(&self, cell: &MapCell, highlight: Option<Highlight>) -> String {
+        match self {
+            Self::Emoji => match highlight {
+                Some(highlight) => format!("\x1b[48;5;{}m{cell}\x1b[49m", highlight.colour()),
+                None => cell.to_string(),
+            },
+            Self::Ascii => {
+                let (text, foreground, mut background) = match cell {
+                    MapCell::Explosion(_, _) => ("##".to_string(), 226, 196),
+                    MapCell::Player(details, terrain) => {
+                        let (text, colour) = tank(details);
+                        (text, colour, terrain_block(terrain).2)
+                    }
+                    MapCell::Shell(_, terrain) => ("()".to_string(), 196, terrain_block(terrain).2),
+                    MapCell::Terrain(terrain) => {
+                        let (text, foreground, background) = terrain_block(terrain);
+                        (text.to_string(), foreground, background)
+                    }
+                    MapCell::Unallocated => ("  ".to_string(), 16, 16),
+                };
+                if let Some(highlight) = highlight {
+                    background = highlight.colour();
+                }
+
+                format!("\x1b[1;38;5;{foreground};48;5;{background}m{text}{RESET}")
+            }
+        }
+    }
+
+    /// Renders the avatar of a tank, as shown in the side panel
+    pub fn avatar(&self, details: &Details) -> String {
+        match self {
+            Self::Emoji => details.avatar.to_string(),
+            Self::Ascii => {
+                let (text, colour) = tank(details);
+                format!("\x1b[1;38;5;{colour}m{text}{RESET}")
+            }
+        }
+    }
+
+    /// Renders a player within a line of text, e.g. in the event feed. Unlike
+    /// the avatar of its tank, it is kept unchanged after the player died.
+    pub fn player(&self, id: PlayerId, avatar: Avatar) -> String {
+        match self {
+            Self::Emoji => avatar.to_string(),
+            Self::Ascii => {
+                let (letter, colour) = letter(id);
+                format!("\x1b[1;38;5;{colour}m{letter}{RESET}")
+            }
+        }
+    }
+
+    /// Renders two characters of a label of the debug overlay, in place of a
+    /// map cell. The characters which may be wider than a column are replaced.
+    pub fn label(&self, text: &str) -> String {
+        let text = text
+            .chars()
+            .map(|c| if c.is_ascii_graphic() { c } else { ' ' })
+            .chain(std::iter::repeat(' '))
+            .take(CELL_WIDTH)
+            .collect::<String>();
+        let background = Highlight::Overlay.colour();
+
+        format!("\x1b[1;38;5;16;48;5;{background}m{text}{RESET}")
+    }
+
+    /// Renders a cell unknown to the inspected tank, on its belief map
+    pub fn unknown(&self) -> String {
+        format!("\x1b[48;5;238m{}{RESET}", " ".repeat(CELL_WIDTH))
+    }
+
+    /// Renders a cell of the outline of the displayed area, on the mini-map
+    pub fn outline(&self) -> String {
+        match self {
+            Self::Emoji => "⬜".to_string(),
+            Self::Ascii => format!("\x1b[1;38;5;16;48;5;231m[]{RESET}"),
+        }
+    }
+}
+
+/// The letter of a player, along with its colour
+fn letter(id: PlayerId) -> (char, u8) {
+    let index = (id as usize).saturating_sub(1);
+
+    (
+        char::from(b'A' + (index % 26) as u8),
+        PLAYER_COLOURS[index % PLAYER_COLOURS.len()],
+    )
+}
+
+/// The letter of a tank followed by its orientation, along with the colour of
+/// its player. Dead tanks are greyed out.
+fn tank(details: &Details) -> (String, u8) {
+    let (letter, colour) = letter(details.id);
+
+    if !details.alive {
+        return (
+            format!("{}x", letter.to_ascii_lowercase()),
+            DEAD_PLAYER_COLOUR,
+        );
+    }
+
+    let heading = match details.orientation {
+        Orientation::North => '^',
+        Orientation::NorthEast | Orientation::SouthWest => '/',
+        Orientation::East => '>',
+        Orientation::SouthEast | Orientation::NorthWest => '\\',
+        Orientation::South => 'v',
+        Orientation::West => '<',
+    };
+
+    (format!("{letter}{heading}"), colour)
+}
+
+/// The text, foreground and background colours of a terrain block
+fn terrain_block(terrain: &Terrain) -> (&'static str, u8, u8) {
+    match terrain {
+        Terrain::Field => ("  ", 16, 28),
+        Terrain::Lake => ("~~", 117, 25),
+        Terrain::Forest(TreeType::Deciduous) => ("&&", 82, 22),
+        Terrain::Forest(TreeType::Evergreen) => ("^^", 82, 22),
+        Terrain::Swamp => (",,", 186, 94),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The visible text of a rendered cell, without the escape sequences
+    fn visible(text: &str) -> String {
+        let mut result = String::new();
+        let mut escaped = false;
+        for c in text.chars() {
+            match c {
+                '\x1b' => escaped = true,
+                'm' if escaped => escaped = false,
+                c if !escaped => result.push(c),
+                _ => {}
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn test_ascii_cells_are_two_columns_wide() {
+        let mut details = Details::new('🦊', 28);
+        details.orientation = Orientation::East;
+        let cells = [
+            MapCell::Player(details, Terrain::Lake),
+            MapCell::Shell(details, Terrain::Field),
+            MapCell::Explosion(details, Terrain::Swamp),
+            MapCell::Terrain(Terrain::Forest(TreeType::Evergreen)),
+            MapCell::Unallocated,
+        ];
+
+        for cell in cells {
+            let text = visible(&Theme::Ascii.cell(&cell, Some(Highlight::Scan)));
+            assert!(text.is_ascii());
+            assert_eq!(2, text.len());
+        }
+
+        // the tank 28 wraps around the alphabet, and keeps its own colour
+        assert_eq!("B>", visible(&Theme::Ascii.avatar(&details)));
+        assert!(Theme::Ascii.avatar(&details).contains("38;5;201m"));
+
+        details.alive = false;
+        assert_eq!("bx", visible(&Theme::Ascii.avatar(&details)));
+        assert_eq!("B", visible(&Theme::Ascii.player(28, '🦊')));
+        assert_eq!("🦊", Theme::Emoji.player(28, '🦊'));
+    }
+
+    #[test]
+    fn test_emoji_cells_are_unchanged() {
+        let cell = MapCell::Terrain(Terrain::Lake);
+        assert_eq!("🟦", Theme::Emoji.cell(&cell, None));
+        assert_eq!(
+            "\x1b[48;5;220m🟦\x1b[49m",
+            Theme::Emoji.cell(&cell, Some(Highlight::Scan))
+        );
+    }
+
+    #[test]
+    fn test_labels_fill_a_cell() {
+        assert_eq!("a ", visible(&Theme::Emoji.label("a")));
+        assert_eq!("x ", visible(&Theme::Ascii.label("xé!")));
+    }
+}
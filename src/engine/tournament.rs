@@ -0,0 +1,236 @@
+//! Formats of a championship, deciding which players meet in every game
+
+use std::collections::{BTreeMap, HashSet};
+
+use crate::api::player::PlayerId;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Every game is played by all the players
+    #[default]
+    FreeForAll,
+    /// Every game is played by a random subset of `size` players
+    RandomSubsets { size: usize },
+    /// Every player meets every other player in a duel
+    RoundRobin,
+    /// Duels between players having similar results so far, over `rounds` rounds
+    Swiss { rounds: u32 },
+    /// Knockout bracket of duels, where a player is out after its first lost duel
+    SingleElimination,
+    /// Knockout bracket of duels, where a player is out after two lost duels
+    DoubleElimination,
+}
+
+impl Format {
+    /// Whether the players meet in duels, rather than in individual games
+    pub fn duels(&self) -> bool {
+        !matches!(self, Self::FreeForAll | Self::RandomSubsets { .. })
+    }
+}
+
+impl std::fmt::Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FreeForAll => write!(f, "Free for all"),
+            Self::RandomSubsets { size } => write!(f, "Random subsets of {size} players"),
+            Self::RoundRobin => write!(f, "Round robin"),
+            Self::Swiss { rounds } => write!(f, "Swiss over {rounds} rounds"),
+            Self::SingleElimination => write!(f, "Single elimination"),
+            Self::DoubleElimination => write!(f, "Double elimination"),
+        }
+    }
+}
+
+/// The outcome of a duel, played over one or more games
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MatchResult {
+    pub players: (PlayerId, PlayerId),
+    pub wins: (u32, u32),
+    /// None for a draw, which only happens outside the elimination formats
+    pub winner: Option<PlayerId>,
+}
+
+/// The final position of a player in a championship
+#[derive(Clone, Debug, PartialEq)]
+pub struct Standing {
+    pub player_id: PlayerId,
+    /// Players with the same points share the same position
+    pub position: u32,
+    /// The average rank for the free-for-all formats (lower is better), the
+    /// match points for round-robin and Swiss (a win is worth 1 point, a draw
+    /// or a bye half a point) and the amount of won duels for eliminations
+    pub points: f32,
+}
+
+/// Orders the players by their points, sharing the positions between equals
+pub fn standings(points: &[(PlayerId, f32)], lower_is_better: bool) -> Vec<Standing> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|(a_id, a), (b_id, b)| {
+        let order = if lower_is_better {
+            a.total_cmp(b)
+        } else {
+            b.total_cmp(a)
+        };
+        order.then(a_id.cmp(b_id))
+    });
+
+    let mut result: Vec<Standing> = Vec::new();
+    for (index, (player_id, points)) in sorted.into_iter().enumerate() {
+        let position = match result.last() {
+            Some(previous) if previous.points == points => previous.position,
+            _ => index as u32 + 1,
+        };
+        result.push(Standing {
+            player_id,
+            position,
+            points,
+        });
+    }
+
+    result
+}
+
+/// Pairs every player with every other player
+pub fn round_robin_pairs(players: &[PlayerId]) -> Vec<(PlayerId, PlayerId)> {
+    let mut result = Vec::new();
+
+    for (index, a) in players.iter().enumerate() {
+        for b in &players[index + 1..] {
+            result.push((*a, *b));
+        }
+    }
+
+    result
+}
+
+/// Pairs the players of a Swiss round, given in the order of their current
+/// standing. Each player meets the next best one it has not met yet, if any.
+/// Returns the pairs, along with the player getting a bye when their count is odd.
+pub fn swiss_pairs(
+    players: &[PlayerId],
+    played: &HashSet<(PlayerId, PlayerId)>,
+    had_bye: &HashSet<PlayerId>,
+) -> (Vec<(PlayerId, PlayerId)>, Option<PlayerId>) {
+    let mut remaining = players.to_vec();
+    let mut pairs = Vec::new();
+
+    // the worst placed player not having had a bye yet gets the bye
+    let bye = if remaining.len() % 2 == 1 {
+        let index = remaining
+            .iter()
+            .rposition(|id| !had_bye.contains(id))
+            .unwrap_or(remaining.len() - 1);
+        Some(remaining.remove(index))
+    } else {
+        None
+    };
+
+    while !remaining.is_empty() {
+        let a = remaining.remove(0);
+        let index = remaining
+            .iter()
+            .position(|b| !played.contains(&(a, *b)) && !played.contains(&(*b, a)))
+            .unwrap_or(0);
+        pairs.push((a, remaining.remove(index)));
+    }
+
+    (pairs, bye)
+}
+
+/// Pairs the players still in an elimination bracket, given in seeding order
+/// along with their lost duels. Players meet others having the same amount
+/// of losses, and the ones left without an opponent meet each other. Returns
+/// the pairs, along with the player getting a bye when their count is odd.
+pub fn elimination_pairs(
+    players: &[(PlayerId, u32)],
+) -> (Vec<(PlayerId, PlayerId)>, Option<PlayerId>) {
+    let mut groups: BTreeMap<u32, Vec<PlayerId>> = BTreeMap::new();
+    for (player_id, losses) in players {
+        groups.entry(*losses).or_default().push(*player_id);
+    }
+
+    let mut pairs = Vec::new();
+    let mut leftovers = Vec::new();
+    for group in groups.values() {
+        for chunk in group.chunks(2) {
+            match chunk {
+                [a, b] => pairs.push((*a, *b)),
+                [a] => leftovers.push(*a),
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    for chunk in leftovers.chunks(2) {
+        if let [a, b] = chunk {
+            pairs.push((*a, *b));
+        }
+    }
+    let bye = if leftovers.len() % 2 == 1 {
+        leftovers.last().copied()
+    } else {
+        None
+    };
+
+    (pairs, bye)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_robin_meets_everybody_once() {
+        let pairs = round_robin_pairs(&[1, 2, 3, 4]);
+
+        assert_eq!(6, pairs.len());
+        for a in 1..=4 {
+            for b in a + 1..=4 {
+                assert!(pairs.contains(&(a, b)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_swiss_avoids_rematches() {
+        let played = HashSet::from([(1, 2)]);
+        let (pairs, bye) = swiss_pairs(&[1, 2, 3, 4, 5], &played, &HashSet::new());
+
+        assert_eq!(vec![(1, 3), (2, 4)], pairs);
+        assert_eq!(Some(5), bye);
+    }
+
+    #[test]
+    fn test_swiss_bye_goes_to_each_player_once() {
+        let (pairs, bye) = swiss_pairs(&[1, 2, 3, 4, 5], &HashSet::new(), &HashSet::from([5]));
+        assert_eq!(vec![(1, 2), (3, 5)], pairs);
+        assert_eq!(Some(4), bye);
+
+        // once everybody had a bye, the worst placed player gets it again
+        let (_, bye) = swiss_pairs(&[1, 2, 3], &HashSet::new(), &HashSet::from([1, 2, 3]));
+        assert_eq!(Some(3), bye);
+    }
+
+    #[test]
+    fn test_elimination_pairs_by_losses() {
+        let (pairs, bye) = elimination_pairs(&[(1, 0), (2, 0), (3, 0), (4, 1), (5, 1)]);
+        assert_eq!(vec![(1, 2), (4, 5)], pairs);
+        assert_eq!(Some(3), bye);
+
+        // the last undefeated player meets the last one with a single loss
+        let (pairs, bye) = elimination_pairs(&[(1, 0), (4, 1)]);
+        assert_eq!(vec![(1, 4)], pairs);
+        assert_eq!(None, bye);
+    }
+
+    #[test]
+    fn test_standings_share_positions() {
+        let result = standings(&[(1, 2.0), (2, 3.0), (3, 3.0), (4, 1.0)], false);
+
+        let positions = result
+            .iter()
+            .map(|standing| (standing.player_id, standing.position))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![(2, 1), (3, 1), (1, 3), (4, 4)], positions);
+    }
+}
@@ -0,0 +1,265 @@
+//! Cropping of the world map to the size of the terminal, so that worlds
+//! larger than the terminal can be watched by scrolling or following a tank
+
+use crate::{
+    api::{
+        map_cell::{MapCell, Terrain},
+        player::PlayerId,
+        position::Position,
+        world_size::{WorldSize, MAX_WORLD_SIZE},
+    },
+    engine::theme::Theme,
+    frame::CELL_WIDTH,
+};
+
+// The columns used by the side panel, next to the map
+const PANEL_WIDTH: usize = 96;
+// Never crop the map below this amount of cells, whatever the terminal size
+const MIN_VISIBLE_CELLS: usize = 10;
+// The maximum width of the mini-map, in cells
+const MINIMAP_WIDTH: usize = 30;
+
+/// A rectangular part of the world
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Area {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+/// The part of the world displayed in the terminal
+#[derive(Debug, Default)]
+pub struct Viewport {
+    x: usize,
+    y: usize,
+    followed: Option<PlayerId>,
+    hide_minimap: bool,
+}
+
+impl Viewport {
+    /// Moves the `current` area by the given amount of cells, and stops
+    /// following any tank
+    pub fn scroll(&mut self, dx: isize, dy: isize, current: &Area, world_size: &WorldSize) {
+        self.followed = None;
+        self.x = current
+            .x
+            .saturating_add_signed(dx)
+            .min(world_size.x - current.width);
+        self.y = current
+            .y
+            .saturating_add_signed(dy)
+            .min(world_size.y - current.height);
+    }
+
+    /// Keeps the given tank, if any, in the centre of the viewport
+    pub fn follow(&mut self, player_id: Option<PlayerId>) {
+        self.followed = player_id;
+    }
+
+    pub fn followed(&self) -> Option<PlayerId> {
+        self.followed
+    }
+
+    pub fn toggle_minimap(&mut self) {
+        self.hide_minimap = !self.hide_minimap;
+    }
+
+    pub fn show_minimap(&self) -> bool {
+        !self.hide_minimap
+    }
+
+    /// Computes the displayed area, of up to `visible` cells. It is centred on
+    /// the position of the followed tank, when there is one.
+    pub fn area(
+        &self,
+        world_size: &WorldSize,
+        visible: (usize, usize),
+        followed_position: Option<&Position>,
+    ) -> Area {
+        let width = visible.0.min(world_size.x);
+        let height = visible.1.min(world_size.y);
+
+        let (x, y) = match followed_position {
+            Some(position) => (
+                position.x.saturating_sub(width / 2),
+                position.y.saturating_sub(height / 2),
+            ),
+            None => (self.x, self.y),
+        };
+
+        Area {
+            x: x.min(world_size.x - width),
+            y: y.min(world_size.y - height),
+            width,
+            height,
+        }
+    }
+}
+
+/// The amount of map cells fitting in the terminal next to the side panel. The
+/// whole world is visible when the size of the terminal is unknown.
+pub fn visible_cells(world_size: &WorldSize) -> (usize, usize) {
+    match crossterm::terminal::size() {
+        Ok((columns, rows)) if columns > 0 && rows > 0 => {
+            let width = (columns as usize).saturating_sub(PANEL_WIDTH) / CELL_WIDTH;
+            // the last row is kept free, so that printing the frame does not scroll
+            let height = (rows as usize).saturating_sub(1);
+            (
+                width.max(MIN_VISIBLE_CELLS).min(world_size.x),
+                height.max(MIN_VISIBLE_CELLS).min(world_size.y),
+            )
+        }
+        _ => (world_size.x, world_size.y),
+    }
+}
+
+/// Draws the whole world at a reduced resolution, in at most `max_rows` rows.
+/// Every cell of the mini-map shows a live tank standing in the block of world
+/// cells it covers, or else the outline of the displayed `area`, or else the
+/// most common terrain of the block.
+pub fn minimap(
+    map: &[[MapCell; MAX_WORLD_SIZE]],
+    world_size: &WorldSize,
+    area: &Area,
+    max_rows: usize,
+    theme: &Theme,
+) -> Vec<String> {
+    if max_rows == 0 {
+        return Vec::new();
+    }
+
+    let scale = world_size
+        .x
+        .div_ceil(MINIMAP_WIDTH)
+        .max(world_size.y.div_ceil(max_rows))
+        .max(1);
+    let (left, right) = (area.x / scale, (area.x + area.width - 1) / scale);
+    let (top, bottom) = (area.y / scale, (area.y + area.height - 1) / scale);
+
+    let mut result = Vec::new();
+    for block_y in 0..world_size.y.div_ceil(scale) {
+        let mut line = String::new();
+
+        for block_x in 0..world_size.x.div_ceil(scale) {
+            let mut tank = None;
+            let mut terrains: Vec<(Terrain, usize)> = Vec::new();
+
+            for row in map
+                .iter()
+                .take(world_size.y)
+                .skip(block_y * scale)
+                .take(scale)
+            {
+                for cell in row
+                    .iter()
+                    .take(world_size.x)
+                    .skip(block_x * scale)
+                    .take(scale)
+                {
+                    let terrain = match cell {
+                        MapCell::Player(details, terrain) => {
+                            if details.alive && tank.is_none() {
+                                tank = Some(*cell);
+                            }
+                            *terrain
+                        }
+                        MapCell::Explosion(_, terrain)
+                        | MapCell::Shell(_, terrain)
+                        | MapCell::Terrain(terrain) => *terrain,
+                        MapCell::Unallocated => continue,
+                    };
+                    match terrains.iter_mut().find(|(entry, _)| *entry == terrain) {
+                        Some((_, count)) => *count += 1,
+                        None => terrains.push((terrain, 1)),
+                    }
+                }
+            }
+
+            let on_outline = ((block_x == left || block_x == right)
+                && (top..=bottom).contains(&block_y))
+                || ((block_y == top || block_y == bottom) && (left..=right).contains(&block_x));
+
+            if let Some(tank) = tank {
+                line += &theme.cell(&tank, None);
+            } else if on_outline {
+                line += &theme.outline();
+            } else {
+                let terrain = terrains
+                    .iter()
+                    .max_by_key(|(_, count)| *count)
+                    .map(|(terrain, _)| *terrain)
+                    .unwrap_or_default();
+                line += &theme.cell(&MapCell::Terrain(terrain), None);
+            }
+        }
+
+        result.push(line);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::player::Details;
+
+    const WORLD_SIZE: WorldSize = WorldSize { x: 100, y: 60 };
+
+    #[test]
+    fn test_scrolling_stays_within_the_world() {
+        let mut viewport = Viewport::default();
+
+        let area = viewport.area(&WORLD_SIZE, (40, 20), None);
+        viewport.scroll(-5, 500, &area, &WORLD_SIZE);
+        let area = viewport.area(&WORLD_SIZE, (40, 20), None);
+        assert_eq!(
+            Area {
+                x: 0,
+                y: 40,
+                width: 40,
+                height: 20
+            },
+            area
+        );
+    }
+
+    #[test]
+    fn test_followed_tank_is_centred() {
+        let mut viewport = Viewport::default();
+        viewport.follow(Some(1));
+
+        let area = viewport.area(&WORLD_SIZE, (40, 20), Some(&Position { x: 50, y: 30 }));
+        assert_eq!((30, 20), (area.x, area.y));
+
+        // close to the border, the viewport stops at the edge of the world
+        let area = viewport.area(&WORLD_SIZE, (40, 20), Some(&Position { x: 98, y: 2 }));
+        assert_eq!((60, 0), (area.x, area.y));
+
+        // scrolling stops following the tank, starting from where it was
+        viewport.scroll(-1, 0, &area, &WORLD_SIZE);
+        assert_eq!(None, viewport.followed());
+        let area = viewport.area(&WORLD_SIZE, (40, 20), None);
+        assert_eq!((59, 0), (area.x, area.y));
+    }
+
+    #[test]
+    fn test_minimap_reduces_the_resolution() {
+        let mut map = Box::new([[MapCell::Terrain(Terrain::Lake); MAX_WORLD_SIZE]; MAX_WORLD_SIZE]);
+        map[59][99] = MapCell::Player(Details::new('🙂', 1), Terrain::Lake);
+        let area = Area {
+            x: 0,
+            y: 0,
+            width: 10,
+            height: 10,
+        };
+
+        // 100 columns need a scale of 4, to fit in 30 cells
+        let lines = minimap(&map[..], &WORLD_SIZE, &area, 20, &Theme::Emoji);
+        assert_eq!(15, lines.len());
+        assert_eq!(25, lines[0].chars().count());
+        assert!(lines[0].starts_with("⬜⬜⬜🟦"));
+        assert!(lines[14].ends_with("🟦🙂"));
+    }
+}
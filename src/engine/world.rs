@@ -1,29 +1,43 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    io,
+    path::Path,
     time::{Duration, Instant},
 };
 
-use rand::{rng, rngs::ThreadRng, seq::SliceRandom, Rng};
+use crossterm::event::KeyEvent;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
 use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
 
 use crate::{
     api::{
         action::Action,
         direction::Direction,
+        game::{GameEnd, GameRules, GameStart, PlayerRng},
         map_cell::{MapCell, Terrain, TreeType},
         orientation::Orientation,
-        player::{Avatar, Details, Player, PlayerId, INVALID_PLAYER},
+        player::{Details, Player, PlayerId, PlayerIdentity, INVALID_PLAYER},
         position::{Position, CARDINAL_SHOT_DISTANCE, POSITIONAL_SHOT_DISTANCE, SCANNING_DISTANCE},
         rotation::Rotation,
         scan::{ScanResult, ScanType},
+        storage::Storage,
         world_size::{WorldSize, MAX_WORLD_SIZE},
     },
     engine::{
-        context::Context,
+        context::{game_rules, Context},
+        event::GameEvent,
+        feed::EventFeed,
+        human::HumanPlayer,
+        inspector::inspect,
         outcome::{PlayerOutcome, TurnOutcome},
+        rewind::{Rewind, WorldSnapshot},
+        seed::player_seed,
         shell::{Shell, ShellState},
-        tank::Tank,
+        tank::{LastScan, Tank},
+        theme::{Highlight, Theme},
+        viewport::{minimap, Area, Viewport},
     },
+    frame::{Frame, CELL_WIDTH},
     terminal::{Terminal, CHAMPIONSHIP_MODE},
 };
 
@@ -38,6 +52,16 @@ const MAX_OBSTACLE_SIZE_PERCENTAGE: f32 = 2.5;
 // the average time of the other players to be too slow.
 const PERFORMANCE_FACTOR: u128 = 10;
 
+// The amount of past turns which can be viewed again in the live viewer
+const REWIND_TURNS: usize = 50;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum MoveState {
+    Pending,
+    Moved,
+    Blocked,
+}
+
 struct ScanRequest {
     requester_id: PlayerId,
     scan_type: ScanType,
@@ -45,30 +69,45 @@ struct ScanRequest {
 
 pub struct World {
     animation: bool,
+    belief: bool,
+    events: Vec<GameEvent>,
+    feed: EventFeed,
     map: Box<[[MapCell; MAX_WORLD_SIZE]; MAX_WORLD_SIZE]>,
     max_turns: usize,
-    rng: ThreadRng,
+    overlay: bool,
+    rejected_sea_worlds: u32,
+    rewind: Rewind<WorldSnapshot>,
+    rng: StdRng,
+    seed: u64,
+    selected: Option<PlayerId>,
     size: WorldSize,
     tanks: HashMap<PlayerId, Tank>,
+    theme: Theme,
     tick: u64,
     turn_number: usize,
+    viewport: Viewport,
+    visible_cells: Option<(usize, usize)>,
 }
 
 impl World {
-    pub fn new(animation: bool, tick: u64, size: WorldSize) -> Self {
+    pub fn new(animation: bool, tick: u64, size: WorldSize, seed: u64) -> Self {
         if size.x > MAX_WORLD_SIZE || size.y > MAX_WORLD_SIZE {
             panic!(
                 "\nWorld size {size} is too big! Maximum accepted size for each dimension is {MAX_WORLD_SIZE}\n\n"
             );
         }
 
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut rejected_sea_worlds = 0;
         loop {
-            let result = World::generate_world(animation, tick, size.clone());
+            let mut result = World::generate_world(animation, tick, size.clone(), seed, rng);
 
             if !result.sea_world() {
+                result.rejected_sea_worlds = rejected_sea_worlds;
                 break result;
             } else {
-                println!("Rejecting sea world...");
+                rejected_sea_worlds += 1;
+                rng = result.rng;
             }
         }
     }
@@ -77,15 +116,163 @@ impl World {
         self.map.clone()
     }
 
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The amount of generated maps rejected for being mostly water, before
+    /// this one was accepted
+    pub fn rejected_sea_worlds(&self) -> u32 {
+        self.rejected_sea_worlds
+    }
+
+    pub fn size(&self) -> WorldSize {
+        self.size.clone()
+    }
+
+    /// The rules under which the game is played
+    pub fn rules(&self) -> GameRules {
+        game_rules(self.max_turns)
+    }
+
+    /// Selects how the map is rendered in the live viewer
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
     pub fn update_animation(&mut self, animation: bool) {
         self.animation = animation;
     }
 
+    /// Selects the amount of map cells fitting in the terminal, to which the
+    /// live view is cropped. By default, the whole world is displayed.
+    pub fn set_visible_cells(&mut self, visible_cells: (usize, usize)) {
+        self.visible_cells = Some(visible_cells);
+    }
+
+    /// Scrolls the displayed part of the map, when it does not fit in the terminal
+    pub fn scroll(&mut self, dx: isize, dy: isize) {
+        let area = self.view_area();
+        self.viewport.scroll(dx, dy, &area, &self.size);
+    }
+
+    /// Centres the displayed part of the map on the next live tank, by player
+    /// id. After the last tank, the map stops following any tank.
+    pub fn follow_next_tank(&mut self) {
+        let mut player_ids = self
+            .tanks
+            .iter()
+            .filter(|(_, tank)| tank.context().health() > 0)
+            .map(|(player_id, _)| *player_id)
+            .collect::<Vec<_>>();
+        player_ids.sort();
+
+        let next = match self.viewport.followed() {
+            Some(followed) => player_ids.into_iter().find(|id| *id > followed),
+            None => player_ids.first().copied(),
+        };
+        self.viewport.follow(next);
+    }
+
+    pub fn toggle_minimap(&mut self) {
+        self.viewport.toggle_minimap();
+    }
+
+    /// Opens the inspector of a tank, and follows it. The selection is kept
+    /// when there is no tank with the given player id.
+    pub fn select_tank(&mut self, player_id: Option<PlayerId>) {
+        match player_id {
+            Some(player_id) if self.tanks.contains_key(&player_id) => {
+                self.selected = Some(player_id);
+                self.viewport.follow(Some(player_id));
+            }
+            Some(_) => {}
+            None => self.selected = None,
+        }
+    }
+
+    /// Shows the world as known by the inspected tank, in place of the true
+    /// map, when its player keeps such a model
+    pub fn toggle_belief_map(&mut self) {
+        self.belief = !self.belief;
+    }
+
+    /// Shows or hides the debug overlay of the inspected tank
+    pub fn toggle_overlay(&mut self) {
+        self.overlay = !self.overlay;
+    }
+
+    /// The live tank of the first human player, if any
+    pub fn human_player(&self) -> Option<PlayerId> {
+        self.live_human_players().first().copied()
+    }
+
+    /// Whether a live human player has not chosen its next action yet
+    pub fn awaits_input(&self) -> bool {
+        self.live_human_players()
+            .iter()
+            .filter_map(|player_id| self.tanks[player_id].human())
+            .any(HumanPlayer::awaits_input)
+    }
+
+    /// Hands a key over to the human players waiting for input, until one of
+    /// them uses it. Returns whether the key was used.
+    pub fn on_key(&mut self, key: KeyEvent) -> bool {
+        self.live_human_players()
+            .iter()
+            .filter_map(|player_id| self.tanks[player_id].human())
+            .any(|player| player.awaits_input() && player.on_key(key))
+    }
+
+    /// Marks the tank of the given player as controlled from the keyboard,
+    /// the keys being handed over to the player through the given handle
+    pub fn set_human_player(&mut self, player_id: PlayerId, human: HumanPlayer) {
+        if let Some(tank) = self.tanks.get_mut(&player_id) {
+            tank.set_human(human);
+        }
+    }
+
+    /// Displays the state of the world one turn earlier, as long as it is
+    /// still recorded. Only the displayed state is rewound, not the players.
+    pub fn step_back(&mut self) -> bool {
+        let snapshot = self
+            .rewind
+            .back(|| WorldSnapshot::capture(&self.map, &self.tanks, self.turn_number));
+
+        match snapshot {
+            Some(snapshot) => {
+                self.restore(snapshot);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Displays the state of the world one turn later, up to the live state
+    pub fn step_forward(&mut self) {
+        if let Some(snapshot) = self.rewind.forward() {
+            self.restore(snapshot);
+        }
+    }
+
+    /// Displays the live state again, after stepping back
+    pub fn return_to_present(&mut self) {
+        if let Some(snapshot) = self.rewind.present() {
+            self.restore(snapshot);
+        }
+    }
+
     pub fn update_tick(&mut self, tick: u64) {
         self.tick = tick;
     }
 
-    pub fn new_turn(&mut self, terminal: &mut Terminal) -> TurnOutcome {
+    /// Plays a turn. Without a terminal, the turn is played headless, without
+    /// any rendering nor delays.
+    pub fn new_turn(&mut self, mut terminal: Option<&mut Terminal>) -> TurnOutcome {
+        // the game always goes on from the live state
+        self.return_to_present();
+        let interactive = terminal.is_some();
+
         let mut turn_outcome = TurnOutcome::new(self.turn_number);
         let (total_cpu_time_per_turn, total_tanks) = self
             .tanks
@@ -105,6 +292,7 @@ impl World {
                 if tank.player().is_ready() && tank.context().health() > 0 {
                     let context = tank.context().clone();
                     let mut action = Action::Idle;
+                    let mut cpu_time = Duration::ZERO;
 
                     let my_cpu_time_per_turn = tank.context().average_cpu_time_per_turn();
                     let others_cpu_time_per_turn =
@@ -113,17 +301,19 @@ impl World {
                     // Checking if the current player wastes too much CPU time
                     // compared to the average CPU time of the other players.
                     if my_cpu_time_per_turn <= others_cpu_time_per_turn * PERFORMANCE_FACTOR {
-                        let api_context = context.clone().into();
+                        let api_context = context.to_api(tank.rng().clone());
+                        tank.logger().set_turn(self.turn_number);
                         let start = Instant::now();
 
                         action = tank.player_mut().act(api_context);
-                        tank.context_mut()
-                            .increase_cpu_time(start.elapsed().as_nanos());
+                        cpu_time = start.elapsed();
+                        tank.context_mut().increase_cpu_time(cpu_time.as_nanos());
                     } else {
                         // Slow players are penalized by skipping their turn,
                         // and setting their action to Idle.
                     }
 
+                    tank.record_action(self.turn_number, action.clone());
                     tank.context_mut().set_previous_action(action.clone());
                     tank.context_mut().set_scanned_data(None);
                     tank.context_mut().set_turn(self.turn_number);
@@ -136,6 +326,7 @@ impl World {
                             context.health(),
                             context.position().clone(),
                             context.score(),
+                            cpu_time,
                         ),
                     ))
                 } else {
@@ -149,32 +340,107 @@ impl World {
             actions.push((a, b));
             turn_outcome.add_player_outcome(a, c);
         }
+        // the tanks are processed in a hash order, so sort their actions
+        actions.sort_by_key(|(player_id, _)| *player_id);
 
-        terminal.move_caret_to_origin();
-        terminal.println(&self);
+        if let Some(terminal) = terminal.as_deref_mut() {
+            terminal.draw(self.render());
+        }
 
         self.process_player_actions(terminal, actions);
+        self.notify_deaths();
+
+        let mut events = std::mem::take(&mut self.events);
+        let mut player_ids = self.tanks.keys().copied().collect::<Vec<_>>();
+        player_ids.sort();
+        for player_id in player_ids {
+            if let Some(tank) = self.tanks.get_mut(&player_id) {
+                events.append(&mut tank.context_mut().take_events());
+            }
+        }
+        self.feed.record(self.turn_number, &events);
+        turn_outcome.add_events(events);
+
+        if interactive {
+            self.record_snapshot();
+        }
 
         turn_outcome
     }
 
-    pub fn spawn_player(&mut self, mut player: Box<dyn Player>, avatar: Avatar) {
+    /// Spawns a player on the map, using the identity allocated by the championship.
+    ///
+    /// The player is handed back if it could not be placed on the map.
+    pub fn spawn_player(
+        &mut self,
+        mut player: Box<dyn Player>,
+        identity: &PlayerIdentity,
+    ) -> Result<(), Box<dyn Player>> {
+        if self.tanks.contains_key(&identity.id) {
+            panic!("Player id {} is already spawned!", identity.id);
+        }
+
         let random = self.get_random_location(MapCell::Terrain(Terrain::Field));
 
         if let Some(position) = random {
             if player.is_ready() && player.initialized() {
-                let player_details = Details::new(avatar, self.tanks.len() as PlayerId + 1);
+                let player_details = Details::new(identity.avatar, identity.id);
                 let context = Context::new(player_details, position, self.size.clone());
 
                 if self
                     .try_set_player_on_cell(player_details, context.position())
                     .is_some()
                 {
+                    let rng = PlayerRng::new(player_seed(self.seed, identity));
                     self.tanks
-                        .insert(player_details.id, Tank::new(player, context));
+                        .insert(player_details.id, Tank::new(player, context, rng));
+                    return Ok(());
                 }
             }
         }
+
+        Err(player)
+    }
+
+    pub fn notify_game_start(
+        &mut self,
+        game_id: u32,
+        roster: &[PlayerIdentity],
+        storage_directory: &Path,
+    ) {
+        let rules = game_rules(self.max_turns);
+
+        for identity in roster {
+            if let Some(tank) = self.tanks.get_mut(&identity.id) {
+                let logger = tank.logger().clone();
+                let rng = tank.rng().clone();
+                tank.player_mut().on_game_start(GameStart {
+                    game_id,
+                    identity: identity.clone(),
+                    logger,
+                    roster: roster.to_vec(),
+                    rules: rules.clone(),
+                    rng,
+                    seed: self.seed,
+                    storage: Storage::open(storage_directory, &identity.name),
+                    world_size: self.size.clone(),
+                });
+            }
+        }
+    }
+
+    pub fn notify_game_end(&mut self, result: &GameEnd) {
+        for tank in self.tanks.values_mut() {
+            tank.player_mut().on_game_end(result.clone());
+        }
+    }
+
+    /// Removes all the players from the world, handing them back
+    pub fn release_players(&mut self) -> Vec<(PlayerId, Box<dyn Player>)> {
+        self.tanks
+            .drain()
+            .map(|(player_id, tank)| (player_id, tank.into_player()))
+            .collect()
     }
 
     pub fn is_game_over(&self) -> bool {
@@ -188,6 +454,25 @@ impl World {
             .count();
     }
 
+    /// Writes the log of every player into `directory`, one file per player
+    pub fn write_logs(&self, directory: &Path, roster: &[PlayerIdentity]) -> io::Result<()> {
+        for identity in roster {
+            if let Some(tank) = self.tanks.get(&identity.id)
+                && !tank.logger().is_empty()
+            {
+                let name = identity
+                    .name
+                    .chars()
+                    .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                    .collect::<String>();
+                tank.logger()
+                    .write_to(&directory.join(format!("{:02}_{name}.log", identity.id)))?;
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn get_ready_players(&self) -> Vec<&Tank> {
         self.tanks
             .iter()
@@ -198,11 +483,33 @@ impl World {
 
 // Private functions
 impl World {
+    fn record_snapshot(&mut self) {
+        let snapshot = WorldSnapshot::capture(&self.map, &self.tanks, self.turn_number);
+        self.rewind.record(snapshot);
+    }
+
+    fn restore(&mut self, snapshot: WorldSnapshot) {
+        self.map = snapshot.map;
+        self.turn_number = snapshot.turn_number;
+        for (player_id, state) in snapshot.tanks {
+            if let Some(tank) = self.tanks.get_mut(&player_id) {
+                tank.restore(state);
+            }
+        }
+    }
+
+    fn notify_deaths(&mut self) {
+        for tank in self.tanks.values_mut() {
+            tank.notify_death();
+        }
+    }
+
     fn process_player_actions(
         &mut self,
-        terminal: &mut Terminal,
+        terminal: Option<&mut Terminal>,
         actions: Vec<(PlayerId, Action)>,
     ) {
+        let mut move_queue = Vec::new();
         let mut shot_queue = Vec::new();
         let mut scan_queue = Vec::new();
 
@@ -212,14 +519,23 @@ impl World {
 
                 match action {
                     Action::Idle => {}
-                    Action::Fire(aim) => shot_queue.push(Shell::new(aim.clone(), tank_position)),
+                    Action::Fire(aim) => {
+                        self.events.push(GameEvent::ShotFired {
+                            shooter_id: *player_id,
+                            aim: aim.clone(),
+                            from: tank_position.clone(),
+                        });
+                        shot_queue.push(Shell::new(*player_id, aim.clone(), tank_position));
+                    }
                     Action::Move(direction) => {
-                        let (from, to) = self.compute_step(
-                            &tank_position,
-                            &tank.context().player_details().orientation,
-                            direction,
-                        );
-                        self.move_player(*player_id, &from, &to);
+                        if tank.context().is_mobile() {
+                            let (from, to) = self.compute_step(
+                                &tank_position,
+                                &tank.context().player_details().orientation,
+                                direction,
+                            );
+                            move_queue.push((*player_id, from, to));
+                        }
                     }
                     Action::Rotate(rotation) => self.rotate_player(*player_id, rotation),
                     Action::Scan(scan_type) => {
@@ -232,6 +548,7 @@ impl World {
             }
         }
 
+        self.process_moves(move_queue);
         self.process_shots(terminal, shot_queue);
         self.update_players_on_world_map(); // we need to update the world map before processing scans
         self.process_scans(scan_queue);
@@ -257,7 +574,7 @@ impl World {
         }
     }
 
-    fn process_shots(&mut self, terminal: &mut Terminal, shot_queue: Vec<Shell>) {
+    fn process_shots(&mut self, mut terminal: Option<&mut Terminal>, shot_queue: Vec<Shell>) {
         let max_iteration = CARDINAL_SHOT_DISTANCE.max(POSITIONAL_SHOT_DISTANCE) + 3;
         let mut possible_shots = Vec::new();
 
@@ -319,13 +636,14 @@ impl World {
                 break;
             }
 
-            if self.animation && !possible_shots.is_empty() {
-                terminal.move_caret_to_origin();
-                terminal.println(self.to_string());
-            }
+            if let Some(terminal) = terminal.as_deref_mut() {
+                if self.animation && !possible_shots.is_empty() {
+                    terminal.draw(self.render());
+                }
 
-            if !CHAMPIONSHIP_MODE {
-                std::thread::sleep(Duration::from_millis(self.tick));
+                if !CHAMPIONSHIP_MODE {
+                    std::thread::sleep(Duration::from_millis(self.tick));
+                }
             }
         }
     }
@@ -401,25 +719,29 @@ impl World {
         if let Some(at) = shell.pos() {
             let (directly_hit, indirectly_hit) = self.get_hit_players(&at);
 
-            if let Some(shooter_details) = self.get_player_at_position(&shell.fired_from()) {
-                let mut reward = 0;
+            let shooter_id = shell.shooter_id();
+            let mut reward = 0;
 
-                for player_id in directly_hit {
-                    if let Some(tank) = self.tanks.get_mut(&player_id) {
-                        reward += tank.context_mut().damage_direct_hit(shooter_details.id);
-                    }
-                }
+            self.events.push(GameEvent::ShellExploded {
+                shooter_id,
+                at: at.clone(),
+            });
 
-                for player_id in indirectly_hit {
-                    if let Some(tank) = self.tanks.get_mut(&player_id) {
-                        reward += tank.context_mut().damage_indirect_hit(shooter_details.id);
-                    }
+            for player_id in directly_hit {
+                if let Some(tank) = self.tanks.get_mut(&player_id) {
+                    reward += tank.context_mut().damage_direct_hit(shooter_id);
                 }
+            }
 
-                if let Some(shooter) = self.tanks.get_mut(&shooter_details.id) {
-                    shooter.context_mut().reward_hits(reward);
+            for player_id in indirectly_hit {
+                if let Some(tank) = self.tanks.get_mut(&player_id) {
+                    reward += tank.context_mut().damage_indirect_hit(shooter_id);
                 }
             }
+
+            if let Some(shooter) = self.tanks.get_mut(&shooter_id) {
+                shooter.context_mut().reward_hits(reward);
+            }
         }
     }
 
@@ -440,44 +762,146 @@ impl World {
         }
     }
 
-    fn move_player(&mut self, player_id: PlayerId, from: &Position, to: &Position) {
-        let to_cell = self.cell_read(to);
+    /// Moves all the tanks at once, so that the outcome does not depend on the
+    /// order in which the moves are processed:
+    /// - a tank driving into a forest stays in place
+    /// - tanks driving into the same cell all stay in place
+    /// - two tanks driving head-on into each other's cell both stay in place
+    /// - a tank driving into a cell which is not vacated during the turn stays
+    ///   in place, which also holds any tanks queued behind it
+    /// - chains and rings of three or more tanks, each driving into the cell
+    ///   vacated by the next one, all move
+    ///
+    /// A tank takes collision damage once for every other tank it bumps into,
+    /// and so does every tank it bumps into.
+    fn process_moves(&mut self, moves: Vec<(PlayerId, Position, Position)>) {
+        let mut paths = BTreeMap::new();
+        let mut states = BTreeMap::new();
+        let mut contenders: BTreeMap<Position, Vec<PlayerId>> = BTreeMap::new();
+        let mut forest_collisions = Vec::new();
+        let mut player_collisions = BTreeSet::new();
+
+        for (player_id, from, to) in moves {
+            // tanks facing the edge of the world go nowhere
+            if from == to {
+                continue;
+            }
 
-        let tank_context = if let Some(tank) = self.tanks.get_mut(&player_id) {
-            Some(tank.context().clone())
-        } else {
-            None
-        };
+            let state = match self.cell_read(&to) {
+                MapCell::Player(..)
+                | MapCell::Terrain(Terrain::Field | Terrain::Lake | Terrain::Swamp) => {
+                    contenders.entry(to.clone()).or_default().push(player_id);
+                    MoveState::Pending
+                }
+                _ => {
+                    forest_collisions.push(player_id);
+                    MoveState::Blocked
+                }
+            };
+            states.insert(player_id, state);
+            paths.insert(player_id, (from, to));
+        }
 
-        if let Some(mut temp_context) = tank_context {
-            if temp_context.is_mobile() {
-                match to_cell {
-                    MapCell::Player(other_details, _) => {
-                        if let Some(other_tank) = self.tanks.get_mut(&other_details.id) {
-                            temp_context.damage_collision_player(other_tank.context_mut());
-                        }
-                    }
-                    MapCell::Terrain(_) => {
-                        if let Some(terrain) =
-                            self.try_set_player_on_cell(*temp_context.player_details(), to)
-                        {
-                            self.unset_player_from_cell(from);
+        for ids in contenders.values().filter(|ids| ids.len() > 1) {
+            for id in ids {
+                states.insert(*id, MoveState::Blocked);
+                for other_id in ids.iter().filter(|other_id| *other_id != id) {
+                    player_collisions.insert((*id, *other_id));
+                }
+            }
+        }
 
-                            temp_context.relocate(to, terrain);
-                        } else {
-                            // only terrain we cannot move into is `Terrain::Forest`
-                            temp_context.damage_collision_forest();
-                        }
-                    }
-                    _ => {}
+        // Follow the chains: a tank moves if its target cell is (or becomes) empty
+        loop {
+            let mut changed = false;
+
+            for (player_id, (_, to)) in paths.iter() {
+                if states[player_id] != MoveState::Pending {
+                    continue;
                 }
 
-                // Update new context
-                if let Some(tank) = self.tanks.get_mut(&player_id) {
-                    tank.set_context(temp_context);
+                let state = match self.get_player_at_position(to).map(|d| d.id) {
+                    None => MoveState::Moved,
+                    Some(occupant_id) => match states.get(&occupant_id) {
+                        Some(MoveState::Pending) => continue,
+                        Some(MoveState::Moved) => MoveState::Moved,
+                        _ => MoveState::Blocked,
+                    },
+                };
+                states.insert(*player_id, state);
+                changed = true;
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        // The pending tanks are now forming rings, where each one drives into the
+        // cell of the next one. Rings of two tanks are head-on collisions.
+        let pending = states
+            .iter()
+            .filter(|(_, state)| **state == MoveState::Pending)
+            .map(|(player_id, _)| *player_id)
+            .collect::<Vec<_>>();
+        for player_id in pending {
+            let mut length = 1;
+            let mut next_id = self
+                .get_player_at_position(&paths[&player_id].1)
+                .map(|d| d.id);
+            while let Some(id) = next_id
+                && id != player_id
+            {
+                next_id = self.get_player_at_position(&paths[&id].1).map(|d| d.id);
+                length += 1;
+            }
+
+            let state = if length == 2 {
+                MoveState::Blocked
+            } else {
+                MoveState::Moved
+            };
+            states.insert(player_id, state);
+        }
+
+        for (player_id, (_, to)) in paths.iter() {
+            if states[player_id] == MoveState::Blocked
+                && let Some(occupant_id) = self.get_player_at_position(to).map(|d| d.id)
+                && states.get(&occupant_id) != Some(&MoveState::Moved)
+            {
+                player_collisions.insert((*player_id, occupant_id));
+                player_collisions.insert((occupant_id, *player_id));
+            }
+        }
+
+        let moved = paths
+            .into_iter()
+            .filter(|(player_id, _)| states[player_id] == MoveState::Moved)
+            .collect::<Vec<_>>();
+        for (_, (from, _)) in moved.iter() {
+            self.unset_player_from_cell(from);
+        }
+        for (player_id, (_, to)) in moved.iter() {
+            if let Some(tank) = self.tanks.get(player_id) {
+                let player_details = *tank.context().player_details();
+                if let Some(terrain) = self.try_set_player_on_cell(player_details, to)
+                    && let Some(tank) = self.tanks.get_mut(player_id)
+                {
+                    tank.context_mut().relocate(to, terrain);
                 }
             }
         }
+
+        for player_id in forest_collisions {
+            if let Some(tank) = self.tanks.get_mut(&player_id) {
+                tank.context_mut().damage_collision_forest();
+            }
+        }
+        for (player_id, other_id) in player_collisions {
+            if let Some(tank) = self.tanks.get_mut(&player_id) {
+                tank.context_mut().damage_collision_player(other_id);
+            }
+        }
     }
 
     fn rotate_player(&mut self, player_id: PlayerId, rotation: &Rotation) {
@@ -486,6 +910,18 @@ impl World {
         }
     }
 
+    /// The ids of the live tanks controlled by humans, in ascending order
+    fn live_human_players(&self) -> Vec<PlayerId> {
+        let mut player_ids = self
+            .tanks
+            .iter()
+            .filter(|(_, tank)| tank.context().health() > 0 && tank.human().is_some())
+            .map(|(player_id, _)| *player_id)
+            .collect::<Vec<_>>();
+        player_ids.sort();
+        player_ids
+    }
+
     fn scan_surroundings(&mut self, scan_request: ScanRequest, world_size: &WorldSize) {
         if let Some(tank) = self.tanks.get(&scan_request.requester_id) {
             let position = tank.context().position().clone();
@@ -495,9 +931,15 @@ impl World {
             if let Some(tank) = self.tanks.get_mut(&scan_request.requester_id) {
                 let scan_result = ScanResult {
                     scan_type: scan_request.scan_type.clone(),
-                    data,
+                    data: data.clone(),
                 };
                 tank.context_mut().set_scanned_data(Some(scan_result));
+                tank.record_scan(LastScan {
+                    turn: self.turn_number,
+                    scan_type: scan_request.scan_type,
+                    from: position,
+                    data,
+                });
             }
         }
     }
@@ -569,6 +1011,19 @@ impl World {
         free_count
     }
 
+    /// The part of the map displayed in the terminal
+    fn view_area(&self) -> Area {
+        let followed_position = self
+            .viewport
+            .followed()
+            .and_then(|player_id| self.tanks.get(&player_id))
+            .filter(|tank| tank.context().health() > 0)
+            .map(|tank| tank.context().position());
+
+        let visible = self.visible_cells.unwrap_or((self.size.x, self.size.y));
+        self.viewport.area(&self.size, visible, followed_position)
+    }
+
     fn count_live_players(&self) -> usize {
         self.tanks
             .iter()
@@ -649,26 +1104,8 @@ impl World {
     ) -> Box<[[MapCell; SCANNING_DISTANCE]; SCANNING_DISTANCE]> {
         let mut sub_map = Box::new([[MapCell::Unallocated; SCANNING_DISTANCE]; SCANNING_DISTANCE]);
 
-        let (pos_x, pos_y, dist) = (
-            position.x as isize,
-            position.y as isize,
-            SCANNING_DISTANCE as isize,
-        );
-
         // remember that position (x,y) and arrays have the axis switched up
-        let (start_j, start_i) = match scan_type {
-            ScanType::Mono(orientation) => match orientation {
-                Orientation::North => (pos_x - dist / 2, pos_y - dist + 1),
-                Orientation::NorthEast => (pos_x, pos_y - dist + 1),
-                Orientation::East => (pos_x, pos_y - dist / 2),
-                Orientation::SouthEast => (pos_x, pos_y),
-                Orientation::South => (pos_x - dist / 2, pos_y),
-                Orientation::SouthWest => (pos_x - dist + 1, pos_y),
-                Orientation::West => (pos_x - dist + 1, pos_y - dist / 2),
-                Orientation::NorthWest => (pos_x - dist + 1, pos_y - dist + 1),
-            },
-            ScanType::Omni => (pos_x - dist / 2, pos_y - dist / 2),
-        };
+        let (start_j, start_i) = scan_origin(scan_type, position);
 
         for i in 0..sub_map.len() {
             let y = start_i + i as isize;
@@ -909,16 +1346,27 @@ impl World {
         percentage >= SEA_WORLD_PERCENTAGE
     }
 
-    fn generate_world(animation: bool, tick: u64, size: WorldSize) -> Self {
+    fn generate_world(animation: bool, tick: u64, size: WorldSize, seed: u64, rng: StdRng) -> Self {
         let mut result = Self {
             animation,
+            belief: false,
+            events: Vec::new(),
+            feed: EventFeed::default(),
             map: Box::new([[MapCell::Unallocated; MAX_WORLD_SIZE]; MAX_WORLD_SIZE]),
             max_turns: compute_game_turns(&size),
-            rng: rng(),
+            overlay: false,
+            rejected_sea_worlds: 0,
+            rewind: Rewind::new(REWIND_TURNS + 1),
+            rng,
+            seed,
+            selected: None,
             size,
             tanks: HashMap::new(),
+            theme: Theme::default(),
             tick,
             turn_number: 0,
+            viewport: Viewport::default(),
+            visible_cells: None,
         };
         result.generate_map_border();
 
@@ -955,87 +1403,286 @@ impl World {
     }
 }
 
-impl std::fmt::Display for World {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl World {
+    /// Renders the displayed part of the map, along with the side panel
+    pub fn render(&self) -> Frame {
+        self.render_area(self.view_area())
+    }
+
+    /// Renders the given part of the map, along with the side panel
+    fn render_area(&self, area: Area) -> Frame {
         const PRINT_OFFSET: usize = 1;
         const HELP_SECTION_OFFSET: usize = PRINT_OFFSET;
-        const STATS_SECTION_OFFSET: usize = HELP_SECTION_OFFSET + 10;
-        const PLAYERS_SECTION_OFFSET: usize = STATS_SECTION_OFFSET + 8;
+        const STATS_SECTION_OFFSET: usize = HELP_SECTION_OFFSET + 15;
+        const PLAYERS_SECTION_OFFSET: usize = STATS_SECTION_OFFSET + 9;
         const PLAYERS_LIST_OFSSET: usize = PLAYERS_SECTION_OFFSET + 2;
+        const FEED_LINES: usize = 6;
 
         let tanks = self.get_tanks();
 
-        for i in 0..self.size.y {
-            let mut line = String::new();
-            for j in 0..self.size.x {
-                line = format!("{line}{}", self.map[i][j]);
+        let selected = self
+            .selected
+            .and_then(|player_id| self.tanks.get(&player_id));
+
+        // the cells covered by the last scan of the inspected tank are highlighted
+        let highlighted = selected
+            .and_then(|tank| tank.last_scan())
+            .map(|scan| scan_origin(&scan.scan_type, &scan.from))
+            .map(|(x, y)| {
+                (
+                    x..x + SCANNING_DISTANCE as isize,
+                    y..y + SCANNING_DISTANCE as isize,
+                )
+            });
+
+        // the debug overlay of the inspected tank is drawn on demand
+        let overlay = selected
+            .filter(|_| self.overlay)
+            .map(|tank| tank.player().debug_overlay())
+            .unwrap_or_default();
+        let marked = overlay.covered_cells().into_iter().collect::<HashSet<_>>();
+
+        let belief = selected
+            .filter(|_| self.belief)
+            .and_then(|tank| tank.player().belief_map());
+
+        // the events are listed below the players, the most recent one last
+        let feed_offset = PLAYERS_LIST_OFSSET + tanks.len() + 1;
+        let feed = self
+            .feed
+            .last_lines(FEED_LINES, self.turn_number, |player_id| {
+                self.theme
+                    .player(player_id, self.tanks[&player_id].avatar())
+            });
+
+        // the inspector replaces the mini-map, which is only useful when the
+        // map does not fit in the terminal
+        let pane_offset = feed_offset + 2 + FEED_LINES + 1;
+        let pane = if let Some(tank) = selected {
+            inspect(tank, &self.theme)
+        } else if self.viewport.show_minimap()
+            && (area.width < self.size.x || area.height < self.size.y)
+        {
+            let mut lines = vec!["[MINI-MAP]".to_string(), "==========".to_string()];
+            lines.extend(minimap(
+                &self.map[..],
+                &self.size,
+                &area,
+                area.height.saturating_sub(pane_offset + 2),
+                &self.theme,
+            ));
+            lines
+        } else {
+            Vec::new()
+        };
+
+        let mut frame = Frame::default();
+        for i in 0..area.height {
+            let y = area.y + i;
+            let mut cells = (area.x..area.x + area.width)
+                .map(|x| {
+                    let position = Position { x, y };
+                    let cell = match belief {
+                        Some(belief) => belief.believed_cell(&position),
+                        None => self.map[y][x],
+                    };
+                    if belief.is_some() && cell == MapCell::Unallocated {
+                        return self.theme.unknown();
+                    }
+
+                    let highlight = if marked.contains(&position) {
+                        Some(Highlight::Overlay)
+                    } else if belief.is_some() && terrain_of(&cell) != terrain_of(&self.map[y][x]) {
+                        Some(Highlight::Drift)
+                    } else if highlighted.as_ref().is_some_and(|(columns, rows)| {
+                        columns.contains(&(x as isize)) && rows.contains(&(y as isize))
+                    }) {
+                        Some(Highlight::Scan)
+                    } else {
+                        None
+                    };
+                    self.theme.cell(&cell, highlight)
+                })
+                .collect::<Vec<_>>();
+
+            // the labels are written over the cells, two characters per cell
+            for (at, text) in overlay.labels.iter().filter(|(at, _)| at.y == y) {
+                let chars = text.chars().collect::<Vec<_>>();
+                for (k, chunk) in chars.chunks(CELL_WIDTH).enumerate() {
+                    let x = at.x + k;
+                    if (area.x..area.x + area.width).contains(&x) {
+                        cells[x - area.x] = self.theme.label(&chunk.iter().collect::<String>());
+                    }
+                }
             }
 
+            let mut panel = String::new();
             if i == HELP_SECTION_OFFSET {
-                line = format!("{line}   [USER KEYS]");
+                panel = "   [USER KEYS]".to_string();
             } else if i == HELP_SECTION_OFFSET + 1 {
-                line = format!("{line}   ===========");
+                panel = "   ===========".to_string();
             } else if i == HELP_SECTION_OFFSET + 2 {
-                line = format!("{line}   Esc  - Interrupt game");
+                panel = "   Esc    - Interrupt game".to_string();
             } else if i == HELP_SECTION_OFFSET + 3 {
-                line = format!("{line}   A    - Toggle shell animation");
+                panel = "   A      - Toggle shell animation".to_string();
             } else if i == HELP_SECTION_OFFSET + 4 {
-                line = format!("{line}   N    - Run next turn and then pause");
+                panel = "   N      - Run next turn and then pause".to_string();
             } else if i == HELP_SECTION_OFFSET + 5 {
-                line = format!("{line}   P    - Toggle pause game");
+                panel = "   P      - Toggle pause game".to_string();
             } else if i == HELP_SECTION_OFFSET + 6 {
-                line = format!("{line}   Up   - Increase tick by 1 millisecond");
+                panel = "   + / -  - Increase / decrease tick by 1 millisecond".to_string();
             } else if i == HELP_SECTION_OFFSET + 7 {
-                line = format!("{line}   Down - Decrease tick by 1 millisecond");
+                panel = "   Arrows - Scroll the map".to_string();
+            } else if i == HELP_SECTION_OFFSET + 8 {
+                panel = "   F      - Follow the next tank".to_string();
+            } else if i == HELP_SECTION_OFFSET + 9 {
+                panel = "   M      - Toggle mini-map".to_string();
+            } else if i == HELP_SECTION_OFFSET + 10 {
+                panel = "   0-99   - Inspect a tank, I - Close the inspector".to_string();
+            } else if i == HELP_SECTION_OFFSET + 11 {
+                panel = "   < / >  - Step back / forward through the last turns".to_string();
+            } else if i == HELP_SECTION_OFFSET + 12 {
+                panel = "   O      - Toggle the debug overlay of the inspected tank".to_string();
+            } else if i == HELP_SECTION_OFFSET + 13 {
+                panel = "   B      - Toggle the belief map of the inspected tank".to_string();
             } else if i == STATS_SECTION_OFFSET {
-                line = format!("{line}   [GAME STATS]");
+                panel = "   [GAME STATS]".to_string();
             } else if i == STATS_SECTION_OFFSET + 1 {
-                line = format!("{line}   ============");
+                panel = "   ============".to_string();
             } else if i == STATS_SECTION_OFFSET + 2 {
                 let a = if self.animation { "on" } else { "off" };
-                line = format!("{line}   Animation:\t\t{}   ", a);
+                panel = format!("   Animation:\t\t{}   ", a);
             } else if i == STATS_SECTION_OFFSET + 3 {
-                line = format!("{line}   Players alive:\t{}   ", self.count_live_players());
+                panel = format!("   Players alive:\t{}   ", self.count_live_players());
             } else if i == STATS_SECTION_OFFSET + 4 {
-                line = format!("{line}   Tick ms:\t\t{}   ", self.tick);
+                panel = format!("   Tick ms:\t\t{}   ", self.tick);
             } else if i == STATS_SECTION_OFFSET + 5 {
-                line = format!(
-                    "{line}   Turn:\t\t{} / {}",
+                let rewound = match self.rewind.turns_back() {
+                    Some(turns) => format!(" (rewound: -{turns})"),
+                    None => String::new(),
+                };
+                panel = format!(
+                    "   Turn:\t\t{} / {}{rewound}          ",
                     self.turn_number, self.max_turns,
                 );
+            } else if i == STATS_SECTION_OFFSET + 6 {
+                let followed = self
+                    .viewport
+                    .followed()
+                    .and_then(|player_id| self.tanks.get(&player_id))
+                    .map(|tank| {
+                        format!(
+                            ", following {}",
+                            self.theme.avatar(tank.context().player_details())
+                        )
+                    })
+                    .unwrap_or_default();
+                let believed = match (belief, selected) {
+                    (Some(_), Some(tank)) => format!(
+                        ", belief map of {}",
+                        self.theme.avatar(tank.context().player_details())
+                    ),
+                    _ => String::new(),
+                };
+                panel = format!(
+                    "   View:\t\t({}, {}) {}x{}{followed}{believed}   ",
+                    area.x, area.y, area.width, area.height
+                );
             } else if i == PLAYERS_SECTION_OFFSET {
-                line = format!("{line}   [ACTIVE PLAYERS]");
+                panel = "   [ACTIVE PLAYERS]".to_string();
             } else if i == PLAYERS_SECTION_OFFSET + 1 {
-                line = format!("{line}   ================");
+                panel = "   ================".to_string();
             } else if i >= PLAYERS_LIST_OFSSET && i < PLAYERS_LIST_OFSSET + tanks.len() {
                 if let Some(&tank) = tanks.get(i - PLAYERS_LIST_OFSSET) {
-                    line = format!(
-                        "{line}   {}: {}",
-                        tank.context().player_details().avatar,
+                    let details = tank.context().player_details();
+                    let marker = if self.selected == Some(details.id) {
+                        '>'
+                    } else {
+                        ' '
+                    };
+                    panel = format!(
+                        " {marker} {:02} {}: {}",
+                        details.id,
+                        self.theme.avatar(details),
                         tank.player().name(),
                     );
                     let name = tank.player().name();
-                    line = match name.chars().count() {
-                        0..9 => format!("{line}\t\t\t"),
-                        9..17 => format!("{line}\t\t"),
-                        17.. => format!("{line}\t"),
+                    panel += match name.chars().count() {
+                        0..9 => "\t\t\t",
+                        9..17 => "\t\t",
+                        17.. => "\t",
                     };
-                    line = format!(
-                        "{line}{:02} pts, {:03}% [{}], {}, {}, {})",
+                    panel += &format!(
+                        "{:02} pts, {:03}% [{}], {}, {}, {})",
                         tank.context().score(),
                         tank.context().health(),
                         tank.health_bar(),
                         tank.context().position(),
                         tank.context().player_details().orientation,
                         tank.context().previous_action(),
-                    )
+                    );
                 }
+            } else if i == feed_offset {
+                panel = "   [EVENTS]".to_string();
+            } else if i == feed_offset + 1 {
+                panel = "   ========".to_string();
+            } else if i >= feed_offset + 2 && i < feed_offset + 2 + feed.len() {
+                panel = format!("   {}", feed[i - feed_offset - 2]);
+            } else if i >= pane_offset && i < pane_offset + pane.len() {
+                panel = format!("   {}", pane[i - pane_offset]);
             }
 
-            writeln!(f, "{line}")?;
+            frame.push_row(cells, panel);
         }
 
-        Ok(())
+        frame
+    }
+}
+
+impl std::fmt::Display for World {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // the whole world, whatever the size of the terminal
+        let area = Area {
+            x: 0,
+            y: 0,
+            width: self.size.x,
+            height: self.size.y,
+        };
+        write!(f, "{}", self.render_area(area))
+    }
+}
+
+/// The terrain of a cell, whatever stands on it
+fn terrain_of(cell: &MapCell) -> Option<Terrain> {
+    match cell {
+        MapCell::Explosion(_, terrain)
+        | MapCell::Player(_, terrain)
+        | MapCell::Shell(_, terrain)
+        | MapCell::Terrain(terrain) => Some(*terrain),
+        MapCell::Unallocated => None,
+    }
+}
+
+/// The top-left corner of the area covered by a scan, which may lie outside of the map
+fn scan_origin(scan_type: &ScanType, position: &Position) -> (isize, isize) {
+    let (pos_x, pos_y, dist) = (
+        position.x as isize,
+        position.y as isize,
+        SCANNING_DISTANCE as isize,
+    );
+
+    match scan_type {
+        ScanType::Mono(orientation) => match orientation {
+            Orientation::North => (pos_x - dist / 2, pos_y - dist + 1),
+            Orientation::NorthEast => (pos_x, pos_y - dist + 1),
+            Orientation::East => (pos_x, pos_y - dist / 2),
+            Orientation::SouthEast => (pos_x, pos_y),
+            Orientation::South => (pos_x - dist / 2, pos_y),
+            Orientation::SouthWest => (pos_x - dist + 1, pos_y),
+            Orientation::West => (pos_x - dist + 1, pos_y - dist / 2),
+            Orientation::NorthWest => (pos_x - dist + 1, pos_y - dist + 1),
+        },
+        ScanType::Omni => (pos_x - dist / 2, pos_y - dist / 2),
     }
 }
 
@@ -1050,7 +1697,14 @@ fn compute_game_turns(world_size: &WorldSize) -> usize {
 mod tests {
 
     use super::*;
-    use crate::engine::game::DEAD_AVATAR;
+    use crate::api::{
+        aiming::Aiming,
+        debug_overlay::DebugOverlay,
+        game::DeathCause,
+        log::Logger,
+        path_finder::{BeliefMap, MapReader},
+        player::DEAD_AVATAR,
+    };
 
     const MINI_MAP_SIZE: usize = 10;
 
@@ -1061,13 +1715,24 @@ mod tests {
         };
         let world = World {
             animation: false,
+            belief: false,
+            events: Vec::new(),
+            feed: EventFeed::default(),
             map: Box::new([[MapCell::Unallocated; MAX_WORLD_SIZE]; MAX_WORLD_SIZE]),
             max_turns: compute_game_turns(&size),
-            rng: rng(),
+            overlay: false,
+            rejected_sea_worlds: 0,
+            rewind: Rewind::new(REWIND_TURNS + 1),
+            rng: StdRng::seed_from_u64(0),
+            seed: 0,
+            selected: None,
             size,
             tanks: HashMap::new(),
+            theme: Theme::default(),
             turn_number: 0,
             tick: 100,
+            viewport: Viewport::default(),
+            visible_cells: None,
         };
 
         Box::new(world)
@@ -1095,6 +1760,157 @@ mod tests {
         world.fill_with_field_cells(&Position { x: 1, y: 1 });
     }
 
+    struct DummyPlayer {
+        ready: bool,
+    }
+
+    impl Player for DummyPlayer {
+        fn act(&mut self, _context: crate::api::context::Context) -> Action {
+            Action::Idle
+        }
+
+        fn name(&self) -> String {
+            "Dummy".to_string()
+        }
+
+        fn is_ready(&self) -> bool {
+            self.ready
+        }
+    }
+
+    struct DeathRecorder {
+        deaths: std::sync::Arc<std::sync::Mutex<Vec<DeathCause>>>,
+    }
+
+    impl Player for DeathRecorder {
+        fn act(&mut self, _context: crate::api::context::Context) -> Action {
+            Action::Idle
+        }
+
+        fn name(&self) -> String {
+            "Dummy".to_string()
+        }
+
+        fn is_ready(&self) -> bool {
+            true
+        }
+
+        fn on_death(&mut self, cause: DeathCause) {
+            self.deaths.lock().unwrap().push(cause);
+        }
+    }
+
+    struct Planner;
+
+    impl Player for Planner {
+        fn act(&mut self, _context: crate::api::context::Context) -> Action {
+            Action::Idle
+        }
+
+        fn name(&self) -> String {
+            "Planner".to_string()
+        }
+
+        fn is_ready(&self) -> bool {
+            true
+        }
+
+        fn debug_overlay(&self) -> DebugOverlay {
+            let mut overlay = DebugOverlay::default();
+            overlay.draw_line(Position { x: 1, y: 1 }, Position { x: 3, y: 1 });
+            overlay.add_label(Position { x: 5, y: 8 }, "hi!");
+            overlay
+        }
+    }
+
+    /// Knows only the row 5 of the map, which it believes to be fields
+    #[derive(Clone)]
+    struct Horizon;
+
+    impl MapReader for Horizon {
+        fn read_at(&self, position: &Position) -> MapCell {
+            if position.y == 5 {
+                MapCell::Terrain(Terrain::Field)
+            } else {
+                MapCell::Unallocated
+            }
+        }
+    }
+
+    struct Believer;
+
+    impl Player for Believer {
+        fn act(&mut self, _context: crate::api::context::Context) -> Action {
+            Action::Idle
+        }
+
+        fn name(&self) -> String {
+            "Believer".to_string()
+        }
+
+        fn is_ready(&self) -> bool {
+            true
+        }
+
+        fn belief_map(&self) -> Option<&dyn BeliefMap> {
+            Some(&Horizon)
+        }
+    }
+
+    #[derive(Default)]
+    struct Chatty {
+        logger: Logger,
+    }
+
+    impl Player for Chatty {
+        fn on_game_start(&mut self, game: GameStart) {
+            self.logger = game.logger;
+        }
+
+        fn act(&mut self, _context: crate::api::context::Context) -> Action {
+            self.logger.log("idling\tagain");
+            Action::Idle
+        }
+
+        fn name(&self) -> String {
+            "Chatty bot".to_string()
+        }
+
+        fn is_ready(&self) -> bool {
+            true
+        }
+    }
+
+    fn dummy_identity(id: PlayerId) -> PlayerIdentity {
+        PlayerIdentity {
+            id,
+            name: "Dummy".to_string(),
+            avatar: DEAD_AVATAR,
+        }
+    }
+
+    #[test]
+    fn spawn_player_keeps_allocated_identity() {
+        let mut world = generate_mini_world();
+        fill_fields(&mut world);
+
+        // A player that is not ready must not consume or shift any id
+        assert!(world
+            .spawn_player(Box::new(DummyPlayer { ready: false }), &dummy_identity(1))
+            .is_err());
+        assert!(world
+            .spawn_player(Box::new(DummyPlayer { ready: true }), &dummy_identity(2))
+            .is_ok());
+
+        assert_eq!(1, world.tanks.len());
+        let tank = world.tanks.get(&2).unwrap();
+        assert_eq!(2, tank.context().player_details().id);
+        assert_eq!(
+            Some(*tank.context().player_details()),
+            world.get_player_at_position(tank.context().position())
+        );
+    }
+
     #[test]
     fn test_compute_step() {
         let mut world = generate_mini_world();
@@ -1155,6 +1971,15 @@ mod tests {
         assert_eq!(100.0, world.compute_terrain_percentage(unallocated_cells));
     }
 
+    #[test]
+    fn test_same_seed_generates_same_map() {
+        let size = WorldSize { x: 40, y: 30 };
+
+        let world = World::new(false, 0, size.clone(), 42);
+        assert!(world.map() == World::new(false, 0, size.clone(), 42).map());
+        assert!(world.map() != World::new(false, 0, size, 43).map());
+    }
+
     #[test]
     fn test_random_field_location() {
         let mut world = generate_mini_world();
@@ -1305,4 +2130,453 @@ mod tests {
         assert!(direct_hit_players_4.is_empty());
         assert!(indirect_hit_players_4.is_empty());
     }
+
+    #[test]
+    fn drowning_player_is_notified_once() {
+        let mut world = generate_mini_world();
+        populate_mini_world(&mut world);
+        fill_fields(&mut world);
+
+        let deaths = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let player = DeathRecorder {
+            deaths: deaths.clone(),
+        };
+        assert!(world
+            .spawn_player(Box::new(player), &dummy_identity(1))
+            .is_ok());
+
+        // Place the tank right next to the lake, and drive it in
+        let from = world.tanks.get(&1).unwrap().context().position().clone();
+        let start = Position { x: 4, y: 3 };
+        world.unset_player_from_cell(&from);
+        let details = *world.tanks.get(&1).unwrap().context().player_details();
+        world.try_set_player_on_cell(details, &start);
+        world
+            .tanks
+            .get_mut(&1)
+            .unwrap()
+            .context_mut()
+            .relocate(&start, Terrain::Field);
+
+        world.process_moves(vec![(1, start, Position { x: 3, y: 3 })]);
+        world.notify_deaths();
+        world.notify_deaths();
+
+        assert_eq!(vec![DeathCause::Drowning], *deaths.lock().unwrap());
+    }
+
+    fn spawn_at(world: &mut Box<World>, id: PlayerId, position: Position) {
+        assert!(world
+            .spawn_player(Box::new(DummyPlayer { ready: true }), &dummy_identity(id))
+            .is_ok());
+
+        let from = world.tanks.get(&id).unwrap().context().position().clone();
+        world.unset_player_from_cell(&from);
+        let details = *world.tanks.get(&id).unwrap().context().player_details();
+        assert!(world.try_set_player_on_cell(details, &position).is_some());
+        world
+            .tanks
+            .get_mut(&id)
+            .unwrap()
+            .context_mut()
+            .relocate(&position, Terrain::Field);
+    }
+
+    fn position_of(world: &World, id: PlayerId) -> Position {
+        world.tanks.get(&id).unwrap().context().position().clone()
+    }
+
+    fn health_of(world: &World, id: PlayerId) -> u8 {
+        world.tanks.get(&id).unwrap().context().health()
+    }
+
+    fn collision_damage() -> u8 {
+        game_rules(0).damage_collision_with_player as u8
+    }
+
+    fn mini_world_with_tanks(positions: &[(PlayerId, Position)]) -> Box<World> {
+        let mut world = generate_mini_world();
+        fill_fields(&mut world);
+        for (id, position) in positions {
+            spawn_at(&mut world, *id, position.clone());
+        }
+
+        world
+    }
+
+    #[test]
+    fn swamp_traps_are_listed_in_the_event_feed() {
+        let mut world =
+            mini_world_with_tanks(&[(1, Position { x: 4, y: 4 }), (2, Position { x: 7, y: 7 })]);
+        world.map[3][4] = MapCell::Terrain(Terrain::Swamp);
+
+        world.process_moves(vec![(1, Position { x: 4, y: 4 }, Position { x: 4, y: 3 })]);
+        world.new_turn(None);
+
+        assert_eq!(
+            vec!["T1 01 got stuck in a swamp".to_string()],
+            world
+                .feed
+                .last_lines(6, world.turn_number, |id| format!("{id:02}"))
+        );
+    }
+
+    #[test]
+    fn inspected_tank_keeps_its_history_and_last_scan() {
+        let mut world =
+            mini_world_with_tanks(&[(1, Position { x: 4, y: 4 }), (2, Position { x: 7, y: 7 })]);
+        world.select_tank(Some(1));
+        world.select_tank(Some(9));
+        assert_eq!(Some(1), world.selected);
+
+        world.new_turn(None);
+        world.scan_surroundings(
+            ScanRequest {
+                requester_id: 1,
+                scan_type: ScanType::Omni,
+            },
+            &world.size.clone(),
+        );
+
+        let tank = world.tanks.get(&1).unwrap();
+        assert_eq!(vec![(1, Action::Idle)], Vec::from(tank.history().clone()));
+        let lines = inspect(tank, &Theme::Emoji);
+        assert!(lines
+            .iter()
+            .any(|line| line.contains("Omni from [x:004, y:004], turn 1")));
+        assert!(lines.iter().any(|line| line.ends_with("Idle")));
+    }
+
+    #[test]
+    fn debug_overlay_is_drawn_for_the_inspected_tank_on_demand() {
+        const OVERLAY: &str = "\x1b[48;5;201m";
+
+        let mut world = mini_world_with_tanks(&[(2, Position { x: 7, y: 7 })]);
+        let identity = PlayerIdentity {
+            id: 1,
+            name: "Planner".to_string(),
+            avatar: '🙂',
+        };
+        assert!(world.spawn_player(Box::new(Planner), &identity).is_ok());
+
+        world.toggle_overlay();
+        assert!(!world.to_string().contains(OVERLAY));
+
+        world.select_tank(Some(1));
+        let frame = world.to_string();
+        assert_eq!(3, frame.matches(OVERLAY).count());
+        assert!(frame.contains(&world.theme.label("hi")));
+        assert!(frame.contains(&world.theme.label("!")));
+
+        world.toggle_overlay();
+        assert!(!world.to_string().contains(OVERLAY));
+    }
+
+    #[test]
+    fn only_the_live_view_is_cropped() {
+        let mut world = mini_world_with_tanks(&[(1, Position { x: 7, y: 7 })]);
+        assert_eq!(MINI_MAP_SIZE, world.render().to_string().lines().count());
+
+        world.set_visible_cells((5, 5));
+        assert_eq!(5, world.render().to_string().lines().count());
+        assert_eq!(MINI_MAP_SIZE, world.to_string().lines().count());
+    }
+
+    #[test]
+    fn human_players_are_waited_for_and_get_the_keys() {
+        let mut world = mini_world_with_tanks(&[(2, Position { x: 7, y: 7 })]);
+        let identity = PlayerIdentity {
+            id: 1,
+            name: "Human".to_string(),
+            avatar: '🙂',
+        };
+        let human = HumanPlayer::new();
+        assert!(world
+            .spawn_player(Box::new(human.clone()), &identity)
+            .is_ok());
+        assert_eq!(None, world.human_player());
+        world.set_human_player(1, human);
+        assert_eq!(Some(1), world.human_player());
+        assert!(world.awaits_input());
+
+        let key = |c| KeyEvent::from(crossterm::event::KeyCode::Char(c));
+        assert!(!world.on_key(key('z')));
+        assert!(world.on_key(key('q')));
+        assert!(!world.awaits_input());
+        // the keys are not taken anymore, once the action is chosen
+        assert!(!world.on_key(key('w')));
+
+        world.new_turn(None);
+        let tank = world.tanks.get(&1).unwrap();
+        assert_eq!(
+            Some(&(1, Action::Scan(ScanType::Omni))),
+            tank.history().back()
+        );
+        assert!(inspect(tank, &Theme::Emoji).contains(&"Scanned area:".to_string()));
+        assert!(world.awaits_input());
+    }
+
+    #[test]
+    fn belief_map_greys_unknown_cells_and_highlights_drift() {
+        const DRIFT: &str = "\x1b[48;5;160m";
+
+        let mut world = mini_world_with_tanks(&[(2, Position { x: 7, y: 7 })]);
+        let identity = PlayerIdentity {
+            id: 1,
+            name: "Believer".to_string(),
+            avatar: '🙂',
+        };
+        assert!(world.spawn_player(Box::new(Believer), &identity).is_ok());
+        world.map[5][3] = MapCell::Terrain(Terrain::Lake);
+        world.select_tank(Some(1));
+        world.toggle_belief_map();
+
+        let frame = world.to_string();
+        let unknown = world.theme.unknown();
+        let drifted = (0..MINI_MAP_SIZE)
+            .filter(|x| terrain_of(&world.map[5][*x]) != Some(Terrain::Field))
+            .count();
+        assert_eq!(
+            MINI_MAP_SIZE * (MINI_MAP_SIZE - 1),
+            frame.matches(&unknown).count()
+        );
+        assert!(drifted >= 1);
+        assert_eq!(drifted, frame.matches(DRIFT).count());
+
+        // the true map is shown again, once the tank is not inspected anymore
+        world.select_tank(None);
+        assert!(!world.to_string().contains(&unknown));
+    }
+
+    #[test]
+    fn player_logs_are_tagged_shown_and_written() {
+        let directory = std::env::temp_dir().join(format!("rbt-logs-{}", std::process::id()));
+        let mut world = mini_world_with_tanks(&[(2, Position { x: 7, y: 7 })]);
+        let identity = PlayerIdentity {
+            id: 1,
+            name: "Chatty bot".to_string(),
+            avatar: '🙂',
+        };
+        assert!(world
+            .spawn_player(Box::<Chatty>::default(), &identity)
+            .is_ok());
+        let roster = vec![identity, dummy_identity(2)];
+
+        world.notify_game_start(1, &roster, &directory);
+        world.new_turn(None);
+
+        let lines = inspect(world.tanks.get(&1).unwrap(), &Theme::Emoji);
+        assert!(lines.contains(&"  turn 0001\tidling again".to_string()));
+
+        world.write_logs(&directory, &roster).unwrap();
+        let content = std::fs::read_to_string(directory.join("01_Chatty_bot.log")).unwrap();
+        assert_eq!("turn 0001\tidling\tagain\n", content);
+        // nothing is written for the players which did not log anything
+        assert!(!directory.join("02_Dummy.log").exists());
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn stepping_back_restores_the_displayed_state_only() {
+        let (from, to) = (Position { x: 4, y: 4 }, Position { x: 5, y: 4 });
+        let mut world = mini_world_with_tanks(&[(1, from.clone()), (2, Position { x: 7, y: 7 })]);
+
+        world.new_turn(None);
+        world.record_snapshot();
+        world.new_turn(None);
+        world.process_moves(vec![(1, from.clone(), to.clone())]);
+        world.record_snapshot();
+
+        assert!(world.step_back());
+        assert_eq!(1, world.turn_number);
+        assert_eq!(from, position_of(&world, 1));
+        assert!(world.get_player_at_position(&to).is_none());
+        // the first recorded turn is the oldest one
+        assert!(!world.step_back());
+
+        world.step_forward();
+        assert_eq!(2, world.turn_number);
+        assert_eq!(to, position_of(&world, 1));
+        assert_eq!(None, world.rewind.turns_back());
+
+        // the game goes on from the live state
+        world.step_back();
+        world.new_turn(None);
+        assert_eq!(3, world.turn_number);
+        assert_eq!(to, position_of(&world, 1));
+    }
+
+    #[test]
+    fn moves_into_the_same_cell_bounce() {
+        let (a, b, target) = (
+            Position { x: 2, y: 7 },
+            Position { x: 4, y: 7 },
+            Position { x: 3, y: 7 },
+        );
+        let mut world = mini_world_with_tanks(&[(1, a.clone()), (2, b.clone())]);
+        let health = health_of(&world, 1);
+
+        world.process_moves(vec![
+            (1, a.clone(), target.clone()),
+            (2, b.clone(), target.clone()),
+        ]);
+
+        assert_eq!(a, position_of(&world, 1));
+        assert_eq!(b, position_of(&world, 2));
+        assert!(world.get_player_at_position(&target).is_none());
+        assert_eq!(health - collision_damage(), health_of(&world, 1));
+        assert_eq!(health - collision_damage(), health_of(&world, 2));
+    }
+
+    #[test]
+    fn head_on_moves_bounce() {
+        let (a, b) = (Position { x: 2, y: 7 }, Position { x: 3, y: 7 });
+        let mut world = mini_world_with_tanks(&[(1, a.clone()), (2, b.clone())]);
+        let health = health_of(&world, 1);
+
+        world.process_moves(vec![(2, b.clone(), a.clone()), (1, a.clone(), b.clone())]);
+
+        assert_eq!(a, position_of(&world, 1));
+        assert_eq!(b, position_of(&world, 2));
+        assert_eq!(health - collision_damage(), health_of(&world, 1));
+        assert_eq!(health - collision_damage(), health_of(&world, 2));
+    }
+
+    #[test]
+    fn chain_of_moves_is_order_independent() {
+        let cells = (1..=4).map(|x| Position { x, y: 7 }).collect::<Vec<_>>();
+        let moves = (0..3)
+            .map(|i| (i as PlayerId + 1, cells[i].clone(), cells[i + 1].clone()))
+            .collect::<Vec<_>>();
+
+        for reversed in [false, true] {
+            let mut world = mini_world_with_tanks(&[
+                (1, cells[0].clone()),
+                (2, cells[1].clone()),
+                (3, cells[2].clone()),
+            ]);
+            let health = health_of(&world, 1);
+
+            let mut queue = moves.clone();
+            if reversed {
+                queue.reverse();
+            }
+            world.process_moves(queue);
+
+            for (id, _, to) in moves.iter() {
+                assert_eq!(*to, position_of(&world, *id));
+                assert_eq!(health, health_of(&world, *id));
+            }
+            assert!(world.get_player_at_position(&cells[0]).is_none());
+        }
+    }
+
+    #[test]
+    fn chain_blocked_by_stationary_tank_stays() {
+        let cells = (1..=3).map(|x| Position { x, y: 7 }).collect::<Vec<_>>();
+        let mut world = mini_world_with_tanks(&[
+            (1, cells[0].clone()),
+            (2, cells[1].clone()),
+            (3, cells[2].clone()),
+        ]);
+        let health = health_of(&world, 1);
+
+        world.process_moves(vec![
+            (1, cells[0].clone(), cells[1].clone()),
+            (2, cells[1].clone(), cells[2].clone()),
+        ]);
+
+        for (index, cell) in cells.iter().enumerate() {
+            assert_eq!(*cell, position_of(&world, index as PlayerId + 1));
+        }
+        assert_eq!(health - collision_damage(), health_of(&world, 1));
+        assert_eq!(health - 2 * collision_damage(), health_of(&world, 2));
+        assert_eq!(health - collision_damage(), health_of(&world, 3));
+    }
+
+    #[test]
+    fn ring_of_moves_rotates() {
+        let ring = [
+            Position { x: 2, y: 7 },
+            Position { x: 3, y: 7 },
+            Position { x: 3, y: 8 },
+            Position { x: 2, y: 8 },
+        ];
+        let mut world = mini_world_with_tanks(
+            &(0..4)
+                .map(|i| (i as PlayerId + 1, ring[i].clone()))
+                .collect::<Vec<_>>(),
+        );
+
+        world.process_moves(
+            (0..4)
+                .map(|i| {
+                    (
+                        i as PlayerId + 1,
+                        ring[i].clone(),
+                        ring[(i + 1) % 4].clone(),
+                    )
+                })
+                .collect(),
+        );
+
+        for i in 0..4 {
+            assert_eq!(ring[(i + 1) % 4], position_of(&world, i as PlayerId + 1));
+        }
+    }
+
+    #[test]
+    fn shell_hits_are_credited_to_the_shooter() {
+        let (shooter, target) = (Position { x: 2, y: 7 }, Position { x: 5, y: 7 });
+        let mut world = mini_world_with_tanks(&[(1, shooter), (2, target.clone())]);
+        let health = health_of(&world, 2);
+
+        // the shell lands on the target, far away from the shooter
+        let shell = Shell::new(1, Aiming::Positional(target.clone()), target);
+        world.compute_shell_damage(&shell);
+
+        assert!(health_of(&world, 2) < health);
+        assert!(world.tanks.get(&1).unwrap().context().score() > 0);
+        assert_eq!(0, world.tanks.get(&2).unwrap().context().score());
+    }
+
+    #[test]
+    fn shells_from_the_same_origin_are_credited_to_each_shooter() {
+        let target = Position { x: 5, y: 7 };
+        let mut world = mini_world_with_tanks(&[
+            (1, Position { x: 2, y: 2 }),
+            (2, Position { x: 8, y: 2 }),
+            (3, target.clone()),
+        ]);
+        let score_of = |world: &World, id| world.tanks.get(&id).unwrap().context().score();
+
+        // both shells land on the target from the same cell, only their
+        // shooters tell them apart
+        let aim = Aiming::Positional(target.clone());
+        world.compute_shell_damage(&Shell::new(1, aim.clone(), target.clone()));
+        world.compute_shell_damage(&Shell::new(2, aim, target));
+
+        let rules = game_rules(0);
+        assert_eq!(0, health_of(&world, 3));
+        assert_eq!(rules.score_direct_hit, score_of(&world, 1));
+        assert_eq!(
+            rules.score_direct_hit + rules.score_killing,
+            score_of(&world, 2)
+        );
+
+        // the earlier hit of the first shooter is credited as an assist
+        let events = world.tanks.get_mut(&3).unwrap().context_mut().take_events();
+        let assists = events
+            .iter()
+            .filter(|event| matches!(event, GameEvent::Assist { .. }))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![&GameEvent::Assist {
+                player_id: 1,
+                victim_id: 3
+            }],
+            assists
+        );
+    }
 }
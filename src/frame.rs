@@ -0,0 +1,153 @@
+//! A rendered screen of the live viewer, compared with the previous one so
+//! that only the cells and panel lines which changed are written again
+
+use std::fmt::Display;
+
+/// Every map cell is two columns wide, whatever the theme
+pub const CELL_WIDTH: usize = 2;
+
+/// The rows of map cells, each one followed by a line of the side panel
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Frame {
+    rows: Vec<Row>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Row {
+    cells: Vec<String>,
+    panel: String,
+}
+
+/// Some text to write at the given position of the screen. When `clear_line`
+/// is set, the rest of the line is cleared beforehand.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Change {
+    pub row: u16,
+    pub column: u16,
+    pub text: String,
+    pub clear_line: bool,
+}
+
+impl Frame {
+    pub fn push_row(&mut self, cells: Vec<String>, panel: String) {
+        self.rows.push(Row { cells, panel });
+    }
+
+    /// Computes the changes turning the `previous` frame into this one. There
+    /// are none when the frames have a different layout, e.g. after resizing
+    /// the terminal, in which case the whole frame has to be drawn again.
+    pub fn changes(&self, previous: &Frame) -> Option<Vec<Change>> {
+        if self.rows.len() != previous.rows.len()
+            || self
+                .rows
+                .iter()
+                .zip(&previous.rows)
+                .any(|(row, old)| row.cells.len() != old.cells.len())
+        {
+            return None;
+        }
+
+        let mut result = Vec::new();
+        for (index, (row, old)) in self.rows.iter().zip(&previous.rows).enumerate() {
+            // the consecutive changed cells are written at once
+            let mut run: Option<Change> = None;
+            for (column, (cell, old_cell)) in row.cells.iter().zip(&old.cells).enumerate() {
+                if cell == old_cell {
+                    result.extend(run.take());
+                    continue;
+                }
+
+                match &mut run {
+                    Some(change) => change.text += cell,
+                    None => {
+                        run = Some(Change {
+                            row: index as u16,
+                            column: (column * CELL_WIDTH) as u16,
+                            text: cell.clone(),
+                            clear_line: false,
+                        })
+                    }
+                }
+            }
+            result.extend(run);
+
+            if row.panel != old.panel {
+                result.push(Change {
+                    row: index as u16,
+                    column: (row.cells.len() * CELL_WIDTH) as u16,
+                    text: row.panel.clone(),
+                    clear_line: true,
+                });
+            }
+        }
+
+        Some(result)
+    }
+}
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in &self.rows {
+            for cell in &row.cells {
+                f.write_str(cell)?;
+            }
+            writeln!(f, "{}", row.panel)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(rows: &[(&[&str], &str)]) -> Frame {
+        let mut result = Frame::default();
+        for (cells, panel) in rows {
+            let cells = cells.iter().map(|cell| cell.to_string()).collect();
+            result.push_row(cells, panel.to_string());
+        }
+        result
+    }
+
+    #[test]
+    fn test_only_the_changes_are_written() {
+        let previous = frame(&[
+            (&["🟩", "🟩", "🟩", "🟩"], "   Turn: 1"),
+            (&["🟦", "🟦", "🟦", "🟦"], "   Tick: 5"),
+        ]);
+        let current = frame(&[
+            (&["🟩", "💥", "🔴", "🟩"], "   Turn: 2"),
+            (&["🙂", "🟦", "🟦", "🟦"], "   Tick: 5"),
+        ]);
+
+        let change = |row, column, text: &str, clear_line| Change {
+            row,
+            column,
+            text: text.to_string(),
+            clear_line,
+        };
+        assert_eq!(
+            Some(vec![
+                change(0, 2, "💥🔴", false),
+                change(0, 8, "   Turn: 2", true),
+                change(1, 0, "🙂", false),
+            ]),
+            current.changes(&previous)
+        );
+        assert_eq!(Some(Vec::new()), current.changes(&current));
+        assert_eq!(
+            "🟩💥🔴🟩   Turn: 2\n🙂🟦🟦🟦   Tick: 5\n",
+            current.to_string()
+        );
+    }
+
+    #[test]
+    fn test_layout_changes_need_a_full_redraw() {
+        let previous = frame(&[(&["🟩", "🟩"], "")]);
+
+        assert_eq!(None, frame(&[(&["🟩"], "")]).changes(&previous));
+        assert_eq!(None, frame(&[]).changes(&previous));
+    }
+}
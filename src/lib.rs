@@ -0,0 +1,14 @@
+//! Rusty Battle Tanks
+//!
+//! The `api` module is the SDK which players are implemented against. The game
+//! itself is driven through the `engine` module, which only exposes what is
+//! needed for running a championship.
+
+#![deny(unsafe_code)]
+
+pub mod api;
+pub mod engine;
+
+mod cast;
+mod frame;
+mod terminal;
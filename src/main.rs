@@ -1,52 +1,213 @@
 #![deny(unsafe_code)]
 
-mod api;
-mod engine;
 mod players;
-mod terminal;
 
-use api::world_size::WorldSize;
-use engine::championship::Championship;
+use std::path::Path;
 
+use rbt::{
+    api::world_size::WorldSize,
+    engine::{
+        Championship, ChampionshipOutcome, Format, PlayerFactory, Theme, DEFAULT_STORAGE_DIRECTORY,
+    },
+};
+
+use players::{
+    alvarez::Luis, armholt::Swede, arola::Arola, fox::TwentyCenturyFox, karjalainen::Miklas,
+    laurikainen::PlayerOne, moykkynen::Joonas, niemisto::Niemisto, pop::Aurelian, rahtu::Rahtu,
+    salonen::Es, siimesjarvi::Siimesjarvi, terava::PlAgiAntti,
+};
+
+// For the duel formats, this is the amount of games in every duel
 const GAME_ROUNDS: u32 = 1;
+const FORMAT: Format = Format::FreeForAll;
+
+// Headless championships run without rendering, playing the games concurrently
+const HEADLESS: bool = false;
 const WORLD_SIZE: WorldSize = WorldSize { x: 120, y: 90 };
 
+// Use Theme::Ascii when the terminal does not render the emoji with a consistent width
+const THEME: Theme = Theme::Emoji;
+
+// Set to true for playing against the bots from the keyboard, in the live viewer
+const HUMAN_PLAYER: bool = false;
+
+const WIPE_STORAGE: bool = false;
+
+const RATINGS_FILE: &str = ".rbt/ratings.tsv";
+
+// The logs of the players are written into this directory after every game
+const LOG_DIRECTORY: &str = ".rbt/logs";
+
+// Set to Some(path) for recording the games into an asciinema cast file
+const CAST_FILE: Option<&str> = None;
+
+// The results are exported as JSON and CSV files into this directory
+const EXPORT_DIRECTORY: &str = ".rbt/results";
+
+// Set to Some(seed) for replaying a previous championship
+const SEED: Option<u64> = None;
+
 fn main() {
-    let mut championship = Championship::new(WORLD_SIZE);
+    let mut championship = Championship::new(WORLD_SIZE, get_players());
+    championship.set_format(FORMAT);
+    championship.set_headless(HEADLESS);
+    championship.set_human_player(HUMAN_PLAYER);
+    championship.set_theme(THEME);
+    championship.set_storage(DEFAULT_STORAGE_DIRECTORY, WIPE_STORAGE);
+    championship.set_ratings_file(RATINGS_FILE);
+    championship.set_log_directory(LOG_DIRECTORY);
+    if let Some(seed) = SEED {
+        championship.set_seed(seed);
+    }
+    if let Some(path) = CAST_FILE {
+        championship.set_cast_file(path);
+    }
     let championship_outcome = championship.run(GAME_ROUNDS);
 
     println!("\n\n");
-    println!("Championship finished after {} rounds\n\n", GAME_ROUNDS);
+    println!("Championship finished after {} rounds\n", GAME_ROUNDS);
+    println!("Format: {}\n", FORMAT);
+    println!("Seed: {}\n\n", championship.seed());
     println!("[RANKING]");
     println!("=========\n");
-    println!("RANK  ID  PLAYER");
-    println!("----  --  -------------------------");
-
-    let mut ranks = championship_outcome.get_ranks();
-
-    while !ranks.is_empty() {
-        let mut player_id = 0;
-        let mut rank = f32::MAX;
-        ranks.iter().for_each(|(p, r)| {
-            if *r <= rank {
-                player_id = *p;
-                rank = *r;
-            }
-        });
-
-        let entry = ranks.remove(&player_id);
-        if let Some(r) = entry {
-            let text = format!(
-                "{:02.02}  {:02}  {}",
-                r,
-                player_id,
-                championship_outcome
-                    .get_player_name(player_id)
-                    .unwrap_or_default()
-            );
-            println!("{text}");
-        }
+    println!("RANK  ID  PLAYER                     POINTS  RATING");
+    println!("----  --  -------------------------  ------  ----------------");
+
+    let rating_changes = championship_outcome.get_rating_changes();
+
+    for standing in championship_outcome.get_standings() {
+        let rating = rating_changes
+            .get(&standing.player_id)
+            .map(|change| format!("{:.0} ({:+.1})", change.after, change.delta()))
+            .unwrap_or_default();
+        let text = format!(
+            "{:>4}  {:02}  {:<25}  {:>6.2}  {}",
+            standing.position,
+            standing.player_id,
+            championship_outcome
+                .get_player_name(standing.player_id)
+                .unwrap_or_default(),
+            standing.points,
+            rating
+        );
+        println!("{text}");
+    }
+
+    println!();
+    if FORMAT.duels() {
+        print_head_to_head(&championship_outcome);
     }
+    println!();
+    print_stats(&championship_outcome);
+
+    match championship_outcome.export(Path::new(EXPORT_DIRECTORY)) {
+        Ok(()) => println!("Results exported to {EXPORT_DIRECTORY}"),
+        Err(e) => eprintln!("Unable to export the results to {EXPORT_DIRECTORY}: {e}"),
+    }
+}
+
+fn print_head_to_head(championship_outcome: &ChampionshipOutcome) {
+    println!("[HEAD TO HEAD]");
+    println!("==============\n");
+    println!("Games won by the player of the row, against the player of the column\n");
+
+    let head_to_head = championship_outcome.head_to_head();
+    let player_ids = championship_outcome
+        .get_standings()
+        .iter()
+        .map(|standing| standing.player_id)
+        .collect::<Vec<_>>();
+
+    let header = player_ids
+        .iter()
+        .map(|id| format!("{id:>4}"))
+        .collect::<String>();
+    println!("ID  PLAYER              {header}");
+
+    for a in &player_ids {
+        let row = player_ids
+            .iter()
+            .map(|b| match head_to_head.get(&(*a, *b)) {
+                _ if a == b => "   -".to_string(),
+                Some(wins) => format!("{wins:>4}"),
+                None => format!("{:>4}", 0),
+            })
+            .collect::<String>();
+        let name = championship_outcome.get_player_name(*a).unwrap_or_default();
+        println!(
+            "{:02}  {:<18}  {row}",
+            a,
+            name.chars().take(18).collect::<String>()
+        );
+    }
+
+    println!();
+}
+
+fn print_stats(championship_outcome: &ChampionshipOutcome) {
+    println!("[STATISTICS]");
+    println!("============\n");
+    println!(
+        "ID  PLAYER                SHOTS  HITS D/I  ACCURACY  DAMAGE +/-  KILLS  ASSISTS  DEATHS S/L/C  TURNS  CPU/TURN  ACTIONS I/F/M/R/S"
+    );
+    println!(
+        "--  --------------------  -----  --------  --------  ----------  -----  -------  ------------  -----  --------  -----------------"
+    );
+
+    let stats = championship_outcome.get_stats();
+    let mut player_ids = stats.keys().copied().collect::<Vec<_>>();
+    player_ids.sort();
+
+    for player_id in player_ids {
+        let s = &stats[&player_id];
+        let name = championship_outcome
+            .get_player_name(player_id)
+            .unwrap_or_default();
+        let hits = format!("{}/{}", s.direct_hits, s.indirect_hits);
+        let damage = format!("{}/{}", s.damage_dealt, s.damage_taken);
+        let deaths = format!(
+            "{}/{}/{}",
+            s.deaths_by_shell, s.deaths_by_lake, s.deaths_by_collision
+        );
+        let actions = format!(
+            "{}/{}/{}/{}/{}",
+            s.actions.idle, s.actions.fire, s.actions.moves, s.actions.rotate, s.actions.scan
+        );
+        let text = format!(
+            "{:02}  {:<20}  {:>5}  {:>8}  {:>7.1}%  {:>10}  {:>5}  {:>7}  {:>12}  {:>5}  {:>6}us  {}",
+            player_id,
+            name.chars().take(20).collect::<String>(),
+            s.shots_fired,
+            hits,
+            s.accuracy() * 100.0,
+            damage,
+            s.kills,
+            s.assists,
+            deaths,
+            s.turns_survived,
+            s.cpu_time_per_turn().as_micros(),
+            actions,
+        );
+        println!("{text}");
+    }
+
+    println!();
+}
 
-    println!("");
+fn get_players() -> Vec<PlayerFactory> {
+    vec![
+        || Box::new(Luis::new()),
+        || Box::new(Swede::new()),
+        || Box::new(Arola::new()),
+        || Box::new(PlayerOne::new()),
+        || Box::new(Joonas::new()),
+        || Box::new(Niemisto::new()),
+        || Box::new(Rahtu::new()),
+        || Box::new(Es::new()),
+        || Box::new(Siimesjarvi::new()),
+        || Box::new(PlAgiAntti::new()),
+        || Box::new(TwentyCenturyFox::new()),
+        || Box::new(Aurelian::new()),
+        || Box::new(Miklas::new()),
+    ]
 }
@@ -12,6 +12,8 @@ use strategy::*;
 use terrain::*;
 use types::*;
 
+use rbt::api::path_finder::BeliefMap;
+
 pub struct Luis {
     id: PId,
     abs_pos: Position,
@@ -41,6 +43,12 @@ impl Player for Luis {
     fn is_ready(&self) -> bool {
         true
     }
+
+    fn belief_map(&self) -> Option<&dyn BeliefMap> {
+        self.strategy
+            .as_ref()
+            .map(|strategy| strategy.map() as &dyn BeliefMap)
+    }
 }
 
 // Private functions
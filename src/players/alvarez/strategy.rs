@@ -408,6 +408,10 @@ impl StrategyManager {
         }
     }
 
+    pub fn map(&self) -> &super::terrain::MappedTerrain {
+        &self.model.map
+    }
+
     pub fn process(&mut self, context: &Context) {
         if context.scanned_data().is_some() {
             self.handle_scan(context);
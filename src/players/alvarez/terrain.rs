@@ -1,5 +1,7 @@
 use super::types::*;
 
+use rand::Rng;
+
 impl MapReader for MappedTerrain {
     fn read_at(&self, position: &Position) -> MapCell {
         let tile = &self.map[position.y][position.x];
@@ -42,7 +44,13 @@ impl Surface {
     }
 }
 
-impl MapCell {
+trait MapCellExt {
+    fn to_surface(&self) -> Surface;
+    fn terrain_danger(&self, t: &Terrain) -> f32;
+    fn positional_danger(&self) -> f32;
+}
+
+impl MapCellExt for MapCell {
     fn to_surface(&self) -> Surface {
         match self {
             MapCell::Terrain(t) => Surface::Terrain(t.clone()),
@@ -246,9 +254,7 @@ impl MappedTerrain {
             }
 
             // Add a small random factor
-            use std::time::SystemTime;
-            let now = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().subsec_nanos();
-            let random_factor = (now as f32 % 1000.0) / 2000.0;
+            let random_factor = context.rng().random_range(0.0..0.5);
             score += random_factor;
 
             if score > best_score {
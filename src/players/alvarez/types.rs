@@ -2,25 +2,22 @@
 #![allow(unused_imports)]
 
 // Just api and common aliases
-pub use crate::{
-    api::{
-        action::Action,
-        aiming::Aiming,
-        context::Context,
-        direction::Direction,
-        map_cell::{MapCell, Terrain, TreeType},
-        orientation::Orientation,
-        path_finder::{MapReader, PathFinder},
-        player::{PlayerId, Player, Details},
-        position::Position,
-        position::SCANNING_DISTANCE,
-        rotation::Rotation,
-        scan::ScanResult,
-        scan::ScanType,
-        world_size::WorldSize,
-        world_size::MAX_WORLD_SIZE,
-    },
-    terminal::Terminal,
+pub use rbt::api::{
+    action::Action,
+    aiming::Aiming,
+    context::Context,
+    direction::Direction,
+    map_cell::{MapCell, Terrain, TreeType},
+    orientation::Orientation,
+    path_finder::{MapReader, PathFinder},
+    player::{PlayerId, Player, Details},
+    position::Position,
+    position::SCANNING_DISTANCE,
+    rotation::Rotation,
+    scan::ScanResult,
+    scan::ScanType,
+    world_size::WorldSize,
+    world_size::MAX_WORLD_SIZE,
 };
 
 pub type Distance = f32;
@@ -28,11 +25,16 @@ pub type PId = PlayerId;
 pub type Turn = usize;
 
 
-impl Orientation {
-    // Existing methods...
+pub trait OrientationExt {
+    fn all() -> [Orientation; 8];
+    fn cardinals() -> [Orientation; 4];
+    #[allow(dead_code)]
+    fn diagonals() -> [Orientation; 4];
+}
 
+impl OrientationExt for Orientation {
     /// Returns all 8 orientations in clockwise order
-    pub fn all() -> [Orientation; 8] {
+    fn all() -> [Orientation; 8] {
         [
             Orientation::North,
             Orientation::NorthEast,
@@ -46,7 +48,7 @@ impl Orientation {
     }
 
     /// Returns the 4 cardinal orientations
-    pub fn cardinals() -> [Orientation; 4] {
+    fn cardinals() -> [Orientation; 4] {
         [
             Orientation::North,
             Orientation::East,
@@ -56,7 +58,7 @@ impl Orientation {
     }
 
     /// Returns the 4 diagonal orientations
-    pub fn diagonals() -> [Orientation; 4] {
+    fn diagonals() -> [Orientation; 4] {
         [
             Orientation::NorthEast,
             Orientation::SouthEast,
@@ -1,4 +1,4 @@
-use crate::api::{
+use rbt::api::{
     action::Action,
     aiming::Aiming,
     context::Context,
@@ -10,7 +10,7 @@ use crate::api::{
     scan::{ScanResult, ScanType},
 };
 
-use rand::random;
+use rand::Rng;
 
 pub struct Swede {
     last_rotation: Rotation,
@@ -82,7 +82,7 @@ impl Swede {
                                     }
                                     //cause some randomness by sometimes turning opposite way
                                     if right_cell == MapCell::Terrain(Terrain::Field) {
-                                        if random::<bool>() {
+                                        if context.rng().random::<bool>() {
                                             self.last_rotation =
                                                 if self.last_rotation == Rotation::Clockwise {
                                                     Rotation::CounterClockwise
@@ -151,7 +151,7 @@ impl Swede {
                                     }
                                     //cause some randomness by sometimes turning opposite way
                                     if lower_cell == MapCell::Terrain(Terrain::Field) {
-                                        if random::<bool>() {
+                                        if context.rng().random::<bool>() {
                                             self.last_rotation =
                                                 if self.last_rotation == Rotation::Clockwise {
                                                     Rotation::CounterClockwise
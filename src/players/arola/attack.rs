@@ -1,5 +1,6 @@
-use crate::api::{action::Action, aiming::Aiming, context::Context, scan::ScanType};
+use rbt::api::{action::Action, aiming::Aiming, context::Context, scan::ScanType};
 
+use super::helpers::ScanResultExt;
 use super::Arola;
 use super::PlayerState;
 
@@ -1,6 +1,6 @@
 use rand::Rng;
 
-use crate::api::{
+use rbt::api::{
     action::Action,
     context::Context,
     direction::Direction,
@@ -11,6 +11,7 @@ use crate::api::{
     world_size::WorldSize,
 };
 
+use super::helpers::{OrientationExt, PositionExt, ScanResultExt};
 use super::Arola;
 use super::PlayerState;
 
@@ -76,7 +77,7 @@ impl Arola {
 
     fn get_moving_action(&mut self, context: &Context) -> Action {
         if self.target_position.is_none() {
-            self.target_position = Some(self.generate_random_position(context.world_size()));
+            self.target_position = Some(self.generate_random_position(context));
         }
 
         let path = self.target_position.as_ref().and_then(|target_position| {
@@ -96,8 +97,9 @@ impl Arola {
         }
     }
 
-    fn generate_random_position(&self, world_size: &WorldSize) -> Position {
-        let mut rng = rand::rng();
+    fn generate_random_position(&self, context: &Context) -> Position {
+        let world_size = context.world_size();
+        let mut rng = context.rng();
         loop {
             let position = Position {
                 x: rng.random_range(1..world_size.x - 1),
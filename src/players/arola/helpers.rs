@@ -1,4 +1,4 @@
-use crate::api::{
+use rbt::api::{
     map_cell::MapCell,
     orientation::Orientation,
     player::{Details, PlayerId, INVALID_PLAYER},
@@ -7,8 +7,12 @@ use crate::api::{
     scan::{ScanResult, ScanType},
 };
 
-impl Position {
-    pub(super) fn get_orientation_to(&self, neighbor: &Position) -> Orientation {
+pub(super) trait PositionExt {
+    fn get_orientation_to(&self, neighbor: &Position) -> Orientation;
+}
+
+impl PositionExt for Position {
+    fn get_orientation_to(&self, neighbor: &Position) -> Orientation {
         match neighbor.manhattan_distance(self) {
             (1, 0) => Orientation::East,
             (1, 1) => Orientation::SouthEast,
@@ -23,8 +27,12 @@ impl Position {
     }
 }
 
-impl Orientation {
-    pub(super) fn quick_turn_bidirectional(&self, other: &Self) -> (Rotation, usize) {
+pub(super) trait OrientationExt {
+    fn quick_turn_bidirectional(&self, other: &Self) -> (Rotation, usize);
+}
+
+impl OrientationExt for Orientation {
+    fn quick_turn_bidirectional(&self, other: &Self) -> (Rotation, usize) {
         let result = self.quick_turn(other);
         if result.1 <= 2 {
             result
@@ -40,7 +48,18 @@ const SCAN_CENTER: usize = SCANNING_DISTANCE / 2;
 const SCAN_BOTTOM: usize = SCANNING_DISTANCE - 1;
 const SCAN_RIGHT: usize = SCANNING_DISTANCE - 1;
 
-impl ScanResult {
+pub(super) trait ScanResultExt {
+    fn get_my_position(&self) -> Position;
+    fn get_player_details(map_cell: &MapCell) -> Details;
+    fn find_other_players(
+        &self,
+        my_id: PlayerId,
+        my_world_position: &Position,
+    ) -> Vec<(Details, Position)>;
+    fn get_world_position(&self, my_world_position: &Position) -> (isize, isize);
+}
+
+impl ScanResultExt for ScanResult {
     fn get_my_position(&self) -> Position {
         match &self.scan_type {
             ScanType::Mono(orientation) => match orientation {
@@ -93,7 +112,7 @@ impl ScanResult {
         }
     }
 
-    pub(super) fn find_other_players(
+    fn find_other_players(
         &self,
         my_id: PlayerId,
         my_world_position: &Position,
@@ -127,7 +146,7 @@ impl ScanResult {
         return other_players;
     }
 
-    pub(super) fn get_world_position(&self, my_world_position: &Position) -> (isize, isize) {
+    fn get_world_position(&self, my_world_position: &Position) -> (isize, isize) {
         let my_scan_position = self.get_my_position();
         let scan_world_x = my_world_position.x as isize - my_scan_position.x as isize;
         let scan_world_y = my_world_position.y as isize - my_scan_position.y as isize;
@@ -1,6 +1,6 @@
 use std::{cmp::max, collections::HashMap};
 
-use crate::api::{
+use rbt::api::{
     map_cell::{MapCell, Terrain, TreeType},
     orientation::Orientation,
     player::INVALID_PLAYER,
@@ -9,6 +9,8 @@ use crate::api::{
     world_size::{WorldSize, MAX_WORLD_SIZE},
 };
 
+use super::helpers::{OrientationExt, PositionExt, ScanResultExt};
+
 pub struct Map {
     map: [[MapCell; MAX_WORLD_SIZE]; MAX_WORLD_SIZE],
 }
@@ -1,4 +1,4 @@
-use crate::api::{
+use rbt::api::{
     action::Action, context::Context, orientation::Orientation, player::Player, position::Position,
     scan::ScanType,
 };
@@ -1,20 +1,16 @@
-use std::sync::Mutex;
-
-use rand::{rngs::ThreadRng, Rng};
-
-use crate::{
-    api::{
-        action::Action,
-        aiming::Aiming,
-        context::Context,
-        direction::Direction,
-        map_cell::{MapCell, Terrain},
-        orientation::Orientation,
-        player::Player,
-        position::{Position, SCANNING_DISTANCE},
-        scan::{ScanResult, ScanType},
-    },
-    terminal::{get_terminal, Terminal},
+use rand::Rng;
+
+use rbt::api::{
+    action::Action,
+    aiming::Aiming,
+    context::Context,
+    direction::Direction,
+    game::PlayerRng,
+    map_cell::{MapCell, Terrain},
+    orientation::Orientation,
+    player::Player,
+    position::{Position, SCANNING_DISTANCE},
+    scan::{ScanResult, ScanType},
 };
 
 const MAX_STEPS: u8 = 3;
@@ -25,7 +21,6 @@ pub struct TwentyCenturyFox {
     scan_pos: Position,
     steps: u8,
     target_orientation: Orientation,
-    _terminal: &'static Mutex<Terminal>,
 }
 
 impl TwentyCenturyFox {
@@ -36,17 +31,16 @@ impl TwentyCenturyFox {
             scan_pos: Position { x: 0, y: 0 },
             steps: 0,
             target_orientation: Orientation::default(),
-            _terminal: get_terminal(),
         }
     }
 }
 
 impl TwentyCenturyFox {
-    fn random_orientation(&mut self, rng: &mut ThreadRng) -> Orientation {
+    fn random_orientation(&mut self, rng: &mut PlayerRng) -> Orientation {
         Orientation::from(rng.random_range(0..Orientation::get_cardinal_direction_count()))
     }
 
-    fn next_state(&mut self, context: Context, rng: &mut ThreadRng) -> Action {
+    fn next_state(&mut self, context: Context, rng: &mut PlayerRng) -> Action {
         match context.previous_action() {
             Action::Idle => Action::Scan(ScanType::Omni),
             Action::Scan(_) => {
@@ -69,7 +63,7 @@ impl TwentyCenturyFox {
         }
     }
 
-    fn move_or_rotate(&mut self, context: &Context, rng: &mut ThreadRng) {
+    fn move_or_rotate(&mut self, context: &Context, rng: &mut PlayerRng) {
         let (mut valid_pos, mut walkable_pos) = self.check_next_pos(context);
 
         let mut x = 0;
@@ -146,7 +140,7 @@ impl Player for TwentyCenturyFox {
     fn act(&mut self, context: Context) -> Action {
         // self.terminal.println(&self.scan_data);
 
-        let mut rng: ThreadRng = rand::rng();
+        let mut rng = context.rng();
         self.next_state(context, &mut rng)
     }
 
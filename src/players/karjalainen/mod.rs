@@ -1,16 +1,19 @@
 mod utils;
 
-use crate::api::map_cell::MapCell;
-use crate::api::scan::ScanResult;
-use crate::api::world_size::MAX_WORLD_SIZE;
-use crate::api::{
+use utils::PositionExt;
+
+use rand::Rng;
+use rbt::api::map_cell::MapCell;
+use rbt::api::scan::ScanResult;
+use rbt::api::world_size::MAX_WORLD_SIZE;
+use rbt::api::{
     action::Action, aiming::Aiming, context::Context, direction::Direction, map_cell,
     player::Player, scan::ScanType,
 };
 
-use crate::api::position::Position;
-use crate::api::position::SCANNING_DISTANCE;
-use crate::api::rotation::Rotation;
+use rbt::api::position::Position;
+use rbt::api::position::SCANNING_DISTANCE;
+use rbt::api::rotation::Rotation;
 
 pub struct Miklas {
     discovered_map: Box<[[MapCell; MAX_WORLD_SIZE]; MAX_WORLD_SIZE]>,
@@ -75,7 +78,7 @@ impl Miklas {
                     moves.push((Action::Scan(ScanType::Mono(orientation)), 500));
                 }
                 MapCell::Terrain(terrain) => {
-                    if terrain == map_cell::Terrain::Field && (rand::random::<i32>() % 5) != 0 {
+                    if terrain == map_cell::Terrain::Field && (context.rng().random::<i32>() % 5) != 0 {
                         moves.push((Action::Move(Direction::Forward), 500));
                     } else {
                         moves.push((Action::Rotate(Rotation::Clockwise), 500));
@@ -173,7 +176,7 @@ impl Miklas {
                 return Action::Scan(ScanType::Omni); // fallback if no actions are available.
             }
         };
-        let mut rng = rand::rng();
+        let mut rng = context.rng();
 
         let select_index = dist.sample(&mut rng);
         // println!(
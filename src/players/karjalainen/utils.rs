@@ -1,8 +1,13 @@
-use crate::api::orientation::Orientation;
-use crate::api::position::Position;
+use rbt::api::orientation::Orientation;
+use rbt::api::position::Position;
 
-impl Position {
-    pub(super) fn direction_normalize(mut self) -> Self {
+pub(super) trait PositionExt {
+    fn direction_normalize(self) -> Self;
+    fn get_orientation_to_pos(&self, target: &Position) -> Orientation;
+}
+
+impl PositionExt for Position {
+    fn direction_normalize(mut self) -> Self {
         if self.x != 0 {
             self.x /= self.x;
         }
@@ -12,7 +17,7 @@ impl Position {
         return self;
     }
 
-    pub(super) fn get_orientation_to_pos(&self, target: &Position) -> Orientation {
+    fn get_orientation_to_pos(&self, target: &Position) -> Orientation {
         match target.manhattan_distance(self) {
             (-1, -1) => Orientation::NorthWest,
             (0, -1) => Orientation::North,
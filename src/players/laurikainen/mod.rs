@@ -3,11 +3,12 @@ use std::collections::HashSet;
 
 use rand::Rng;
 
-use crate::api::{
+use rbt::api::{
     action::Action,
     aiming::Aiming,
     context::Context,
     direction::Direction,
+    game::PlayerRng,
     map_cell::{MapCell, Terrain},
     orientation::Orientation,
     player::{Details, Player},
@@ -53,6 +54,7 @@ impl Player for PlayerOne {
         let mut have_an_action = true;
         let pos_x = context.position().x;
         let pos_y = context.position().y;
+        let mut rng = context.rng();
 
         let mut action = if let Some(scan_action) = self.do_something(context) {
             scan_action
@@ -62,8 +64,8 @@ impl Player for PlayerOne {
         };
 
         if !have_an_action {
-            if self.should_move() {
-                action = self.handle_movement();
+            if self.should_move(&mut rng) {
+                action = self.handle_movement(&mut rng);
             } else {
                 action = Action::Idle;
             }
@@ -592,7 +594,7 @@ impl PlayerOne {
         }
     }
 
-    fn handle_movement(&self) -> Action {
+    fn handle_movement(&self, rng: &mut PlayerRng) -> Action {
         let default_prob = 0.5;
         let rotate_prob = match self.last_action {
             Action::Idle => default_prob,
@@ -602,16 +604,16 @@ impl PlayerOne {
             Action::Scan(_) => default_prob,
         };
 
-        let rotate = rand::rng().random_bool(rotate_prob);
+        let rotate = rng.random_bool(rotate_prob);
         if rotate {
-            let rotate_clockwise = rand::rng().random_bool(0.5);
+            let rotate_clockwise = rng.random_bool(0.5);
             if rotate_clockwise {
                 Action::Rotate(Rotation::Clockwise)
             } else {
                 Action::Rotate(Rotation::CounterClockwise)
             }
         } else {
-            let forward = rand::rng().random_bool(0.8);
+            let forward = rng.random_bool(0.8);
             if forward {
                 Action::Move(Direction::Forward)
             } else {
@@ -620,9 +622,9 @@ impl PlayerOne {
         }
     }
 
-    fn should_move(&self) -> bool {
+    fn should_move(&self, rng: &mut PlayerRng) -> bool {
         // check last scan result etc.
-        rand::rng().random_bool(0.8)
+        rng.random_bool(0.8)
     }
 }
 
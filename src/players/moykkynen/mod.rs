@@ -1,4 +1,4 @@
-use crate::api::{
+use rbt::api::{
     action::Action,
     aiming::Aiming,
     context::Context,
@@ -350,7 +350,7 @@ impl Joonas {
         Action::Scan(self.prev_scan_type.clone())
     }
 
-    fn decide_firing_option(&mut self, enemy: Enemy) -> Action {
+    fn decide_firing_option(&mut self, ctx: &Context, enemy: Enemy) -> Action {
         // these are the ranges for omni and mono scans and therefore the ranges of positional and cardinal shooting
         let positional_range = SCANNING_DISTANCE / 2;
         let cardinal_range = SCANNING_DISTANCE - 1;
@@ -376,19 +376,18 @@ impl Joonas {
             && enemy.pos_history.all_equal()
             && self.last_action == Action::Scan(ScanType::Omni)
         {
-            return self.explore();
+            return self.explore(ctx);
         }
         self.decide_next_scan_type(Some(self.get_enemy_direction(&enemy.position)))
     }
 
-    fn decide_rotation_direction(&mut self) -> Action {
+    fn decide_rotation_direction(&mut self, ctx: &Context) -> Action {
         // If we rotated as last action and still need to rotate, keep rotating in the same direction. Otherwise random.
         match self.ongoing_turn {
             Some(Rotation::Clockwise) => Action::Rotate(Rotation::Clockwise),
             Some(Rotation::CounterClockwise) => Action::Rotate(Rotation::CounterClockwise),
             None => {
-                let mut rng = rand::rng();
-                if rng.random_bool(0.5) {
+                if ctx.rng().random_bool(0.5) {
                     self.ongoing_turn = Some(Rotation::Clockwise);
                     Action::Rotate(Rotation::Clockwise)
                 } else {
@@ -401,13 +400,13 @@ impl Joonas {
 
     // Move forward if possible, otherwise turn to a random direction until no obstacle ahead
     // TODO: some smarter movement?
-    fn explore(&mut self) -> Action {
+    fn explore(&mut self, ctx: &Context) -> Action {
         match self.get_terrain_ahead() {
             MapCell::Terrain(Terrain::Field) => Action::Move(Direction::Forward),
-            MapCell::Terrain(Terrain::Forest(_)) => self.decide_rotation_direction(),
-            MapCell::Terrain(Terrain::Lake) => self.decide_rotation_direction(),
-            MapCell::Terrain(Terrain::Swamp) => self.decide_rotation_direction(),
-            MapCell::Player(_, _) => self.decide_rotation_direction(),
+            MapCell::Terrain(Terrain::Forest(_)) => self.decide_rotation_direction(ctx),
+            MapCell::Terrain(Terrain::Lake) => self.decide_rotation_direction(ctx),
+            MapCell::Terrain(Terrain::Swamp) => self.decide_rotation_direction(ctx),
+            MapCell::Player(_, _) => self.decide_rotation_direction(ctx),
             MapCell::Unallocated => Action::Scan(ScanType::Mono(self.orientation)),
             _ => Action::Fire(Aiming::Cardinal(self.orientation)),
             // TODO: handle explosion/shell somehow?
@@ -420,9 +419,9 @@ impl Joonas {
 
         // If (alive) enemies around, shoot at them. Otherwise act based on what is ahead.
         if let Some(enemy) = self.check_enemy_data() {
-            self.decide_firing_option(enemy)
+            self.decide_firing_option(ctx, enemy)
         } else {
-            self.explore()
+            self.explore(ctx)
         }
     }
 }
@@ -1,9 +1,9 @@
-use crate::api::action::Action;
-use crate::api::aiming::Aiming;
-use crate::api::context::Context;
-use crate::api::orientation::Orientation;
-use crate::api::player::Player;
-use crate::api::rotation::Rotation;
+use rbt::api::action::Action;
+use rbt::api::aiming::Aiming;
+use rbt::api::context::Context;
+use rbt::api::orientation::Orientation;
+use rbt::api::player::Player;
+use rbt::api::rotation::Rotation;
 
 use std::io::Read;
 use std::net::TcpStream;
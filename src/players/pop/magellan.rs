@@ -1,4 +1,4 @@
-use crate::api::{
+use rbt::api::{
     action::Action,
     direction::Direction,
     map_cell::{MapCell, Terrain},
@@ -1,25 +1,21 @@
 mod magellan;
 
-use std::sync::Mutex;
-
 use magellan::Magellan;
 
-use crate::{
-    api::{
-        action::Action,
-        aiming::Aiming,
-        context::Context,
-        direction::Direction,
-        map_cell::{MapCell, Terrain},
-        orientation::Orientation,
-        path_finder::{MapReader, PathFinder},
-        player::{Details, Player},
-        position::{Position, CARDINAL_SHOT_DISTANCE, SCANNING_DISTANCE},
-        rotation::Rotation,
-        scan::{ScanResult, ScanType},
-        world_size::WorldSize,
-    },
-    terminal::{get_terminal, Terminal},
+use rbt::api::{
+    action::Action,
+    aiming::Aiming,
+    context::Context,
+    debug_overlay::DebugOverlay,
+    direction::Direction,
+    map_cell::{MapCell, Terrain},
+    orientation::Orientation,
+    path_finder::{BeliefMap, MapReader, PathFinder},
+    player::{Details, Player},
+    position::{Position, CARDINAL_SHOT_DISTANCE, SCANNING_DISTANCE},
+    rotation::Rotation,
+    scan::{ScanResult, ScanType},
+    world_size::WorldSize,
 };
 
 const MAX_CONSECUTIVE_FORWARD_SCANS: usize = 4;
@@ -37,7 +33,6 @@ pub struct Aurelian {
     previous_enemy_positions: Vec<Position>,
     scan_turn: usize,
     shot_queue: Vec<Action>,
-    terminal: &'static Mutex<Terminal>,
     unreachable_positions: Vec<Position>,
     walking_direction: Direction,
     walking_path: Vec<Position>,
@@ -56,7 +51,6 @@ impl Aurelian {
             previous_enemy_positions: Vec::new(),
             scan_turn: 0,
             shot_queue: Vec::new(),
-            terminal: get_terminal(),
             unreachable_positions: Vec::new(),
             walking_direction: Direction::default(),
             walking_path: Vec::new(),
@@ -486,8 +480,7 @@ impl Aurelian {
 
     #[allow(dead_code)]
     fn print(&mut self, context: &Context, highlights: Vec<Position>) {
-        let mut terminal = self.terminal.lock().unwrap();
-        terminal.println("+++++++++++++++++++++++");
+        println!("+++++++++++++++++++++++");
 
         for i in 0..context.world_size().y {
             let mut line = String::new();
@@ -508,9 +501,9 @@ impl Aurelian {
                     line = format!("{line}{cell}");
                 }
             }
-            terminal.println(line);
+            println!("{line}");
         }
-        terminal.println("-----------------------");
+        println!("-----------------------");
     }
 }
 
@@ -532,4 +525,14 @@ impl Player for Aurelian {
     fn is_ready(&self) -> bool {
         true
     }
+
+    fn debug_overlay(&self) -> DebugOverlay {
+        let mut overlay = DebugOverlay::default();
+        overlay.mark_path(&self.walking_path);
+        overlay
+    }
+
+    fn belief_map(&self) -> Option<&dyn BeliefMap> {
+        Some(&self.map_reader)
+    }
 }
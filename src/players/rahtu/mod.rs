@@ -1,6 +1,7 @@
-use crate::api::{
+use rbt::api::{
     action::Action,
     context::Context,
+    path_finder::BeliefMap,
     player::Player
 };
 
@@ -46,4 +47,7 @@ impl Player for Rahtu {
     fn is_ready(&self) -> bool {
         true
     }
+    fn belief_map(&self) -> Option<&dyn BeliefMap> {
+        Some(&self.data.map)
+    }
 }
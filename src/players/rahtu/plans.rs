@@ -1,4 +1,6 @@
-use crate::{api::{action::Action, aiming::Aiming, context::Context, orientation::Orientation, path_finder::PathFinder, position::Position, scan::ScanType}, WORLD_SIZE};
+use rbt::api::{action::Action, aiming::Aiming, context::Context, orientation::Orientation, path_finder::PathFinder, position::Position, scan::ScanType};
+
+use crate::WORLD_SIZE;
 use super::shared::Data;
 
 pub fn get_next_action(data: &mut Data, context: &Context) -> Action {
@@ -1,8 +1,10 @@
 use std::collections::VecDeque;
 
-use crate::{api::{
+use rbt::api::{
     context::Context, map_cell::{MapCell, Terrain}, orientation::Orientation, path_finder::MapReader, player::Details, position::{Position, SCANNING_DISTANCE}, scan::ScanType, world_size::MAX_WORLD_SIZE
-}, WORLD_SIZE};
+};
+
+use crate::WORLD_SIZE;
 
 use super::shared::{self, Map, SensorData, Track};
 
@@ -299,11 +301,11 @@ impl Map {
 }
 
 impl MapReader for Map {
-    fn read_at(&self, position: &crate::api::position::Position) -> MapCell {
+    fn read_at(&self, position: &rbt::api::position::Position) -> MapCell {
         match self.get_map_tile(position.x as isize, position.y as isize) {
             SensorData::NotScanned => MapCell::Unallocated,
             SensorData::Empty => MapCell::Terrain(Terrain::Field),
-            SensorData::Blocked => MapCell::Terrain(Terrain::Forest(crate::api::map_cell::TreeType::Deciduous)),
+            SensorData::Blocked => MapCell::Terrain(Terrain::Forest(rbt::api::map_cell::TreeType::Deciduous)),
         }
     }
 }
\ No newline at end of file
@@ -1,6 +1,6 @@
 use std::collections::VecDeque;
 
-use crate::api::world_size::MAX_WORLD_SIZE;
+use rbt::api::world_size::MAX_WORLD_SIZE;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum SensorData {
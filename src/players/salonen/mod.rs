@@ -1,4 +1,4 @@
-use crate::api::{
+use rbt::api::{
     action::Action,
     context::Context,
     direction::Direction,
@@ -151,7 +151,7 @@ impl Player for Es {
                     _ => {
                         if self.rotate_direction.is_none()
                         {
-                            let mut rng = rand::rng();
+                            let mut rng = context.rng();
                             let dir = if rng.random_bool(0.5)
                             {
                                 Rotation::Clockwise
@@ -1,4 +1,4 @@
-use crate::api::{
+use rbt::api::{
     action::Action,
     aiming::Aiming,
     context::Context,
@@ -424,7 +424,7 @@ impl AdvancedStrategy {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::engine::game::{avatar, DEAD_AVATAR};
+    use rbt::api::player::DEAD_AVATAR;
 
     #[test]
     fn check_initial_player_values() {
@@ -458,7 +458,7 @@ mod tests {
                 [[MapCell::Terrain(Terrain::Field); SCANNING_DISTANCE]; SCANNING_DISTANCE],
             ),
         };
-        let my_player_details = Details::new(avatar(1), 1);
+        let my_player_details = Details::new('🙂', 1);
 
         scan_result.data[1][1] = MapCell::Player(my_player_details, Terrain::Field);
 
@@ -477,7 +477,7 @@ mod tests {
                 [[MapCell::Terrain(Terrain::Field); SCANNING_DISTANCE]; SCANNING_DISTANCE],
             ),
         };
-        let my_player_details = Details::new(avatar(1), 1);
+        let my_player_details = Details::new('🙂', 1);
         // Should be false when completely empty
         assert_eq!(
             false,
@@ -505,7 +505,7 @@ mod tests {
         );
 
         // Should be true if there is alive player other than myself
-        scan_result.data[1][3] = MapCell::Player(Details::new(avatar(1), 2), Terrain::Field);
+        scan_result.data[1][3] = MapCell::Player(Details::new('🙂', 2), Terrain::Field);
         assert_eq!(
             true,
             s.are_other_players_in_scan_result(my_player_details.id, &scan_result)
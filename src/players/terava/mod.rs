@@ -1,4 +1,5 @@
-use crate::api::{
+use rand::Rng;
+use rbt::api::{
     action::Action, aiming::Aiming, context::Context, direction::Direction,
     orientation::Orientation, player::Player, scan::ScanType,
 };
@@ -10,14 +11,14 @@ impl PlAgiAntti {
         Self {}
     }
 
-    fn evaluate_threat(&self, _context: &Context) -> u32 {
+    fn evaluate_threat(&self, context: &Context) -> u32 {
         // TODO: Implement logic to assess the threat level
-        rand::random::<u32>() % 2
+        context.rng().random::<u32>() % 2
     }
 
-    fn evaluate_opportunity(&self, _context: &Context) -> u32 {
+    fn evaluate_opportunity(&self, context: &Context) -> u32 {
         // TODO: Implement logic to assess the opportunity level
-        rand::random::<u32>() % 2
+        context.rng().random::<u32>() % 2
     }
 
     fn defensive_action(&self, _context: &Context) -> Action {
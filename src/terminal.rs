@@ -1,6 +1,7 @@
 use std::{
     fmt::Display,
-    io::{stdout, Stdout, Write},
+    io::{self, stdout, Stdout, Write},
+    path::Path,
     sync::{Mutex, OnceLock},
 };
 
@@ -10,6 +11,8 @@ use crossterm::{
     ExecutableCommand, QueueableCommand,
 };
 
+use crate::{cast::Cast, frame::Frame};
+
 static INSTANCE: OnceLock<Mutex<Terminal>> = OnceLock::new();
 
 pub fn get_terminal() -> &'static Mutex<Terminal> {
@@ -26,9 +29,34 @@ pub const DEBUG_MODE: bool = false;
 /// For debugging purposes, mostly when console output scrolling is seen as beneficial,
 /// the `DEBUG_MODE` flag can be enabled, which will disable the wrapping in effect.
 pub struct Terminal {
+    // the last drawn frame, which is still on the screen
+    previous: Option<Frame>,
+    stdout: Output,
+}
+
+/// The standard output, optionally teed into a cast recording
+struct Output {
+    cast: Option<Cast>,
     stdout: Stdout,
 }
 
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.stdout.write(buf)?;
+        if let Some(cast) = &mut self.cast {
+            cast.record(&buf[..written]);
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if let Some(cast) = &mut self.cast {
+            cast.flush_frame()?;
+        }
+        self.stdout.flush()
+    }
+}
+
 impl Terminal {
     /// This is meant for the game engine, players should avoid using it.
     pub fn enter_raw_mode() {
@@ -48,6 +76,7 @@ impl Terminal {
 
     pub fn clear_screen(&mut self) {
         if !CHAMPIONSHIP_MODE && !DEBUG_MODE {
+            self.previous = None;
             let _ = self.stdout.queue(Clear(ClearType::All));
             self.move_caret_to_origin();
         }
@@ -55,6 +84,7 @@ impl Terminal {
 
     pub fn clear_below(&mut self) {
         if !CHAMPIONSHIP_MODE && !DEBUG_MODE {
+            self.previous = None;
             let _ = self.stdout.execute(Clear(ClearType::FromCursorDown));
         }
     }
@@ -65,6 +95,55 @@ impl Terminal {
         }
     }
 
+    /// Starts teeing every frame into an asciinema cast file at `path`.
+    /// This is meant for the game engine, players should avoid using it.
+    pub fn start_recording(&mut self, path: &Path) -> io::Result<()> {
+        self.stdout.cast = Some(Cast::create(path)?);
+        Ok(())
+    }
+
+    /// This is meant for the game engine, players should avoid using it.
+    pub fn stop_recording(&mut self) {
+        let _ = self.stdout.flush();
+        self.stdout.cast = None;
+    }
+
+    /// Draws a frame from the top of the screen. Only the cells and panel
+    /// lines which changed since the previous frame are written.
+    pub fn draw(&mut self, frame: Frame) {
+        if CHAMPIONSHIP_MODE {
+            return;
+        }
+        if DEBUG_MODE {
+            println!("{frame}");
+            return;
+        }
+
+        match self
+            .previous
+            .as_ref()
+            .and_then(|previous| frame.changes(previous))
+        {
+            Some(changes) => {
+                for change in changes {
+                    let _ = self.stdout.queue(MoveTo(change.column, change.row));
+                    if change.clear_line {
+                        let _ = self.stdout.queue(Clear(ClearType::UntilNewLine));
+                    }
+                    let _ = write!(self.stdout, "{}", change.text);
+                }
+                let _ = self.stdout.flush();
+            }
+            None => {
+                let _ = self.stdout.queue(MoveTo(0, 0));
+                self.println_text(frame.to_string());
+                let _ = self.stdout.execute(Clear(ClearType::FromCursorDown));
+            }
+        }
+
+        self.previous = Some(frame);
+    }
+
     pub fn println<T: Display>(&mut self, printable: T) {
         if !CHAMPIONSHIP_MODE {
             if DEBUG_MODE {
@@ -79,10 +158,17 @@ impl Terminal {
 // Private functions
 impl Terminal {
     fn new() -> Self {
-        Self { stdout: stdout() }
+        Self {
+            previous: None,
+            stdout: Output {
+                cast: None,
+                stdout: stdout(),
+            },
+        }
     }
 
     fn println_text(&mut self, text: String) {
+        self.previous = None;
         for line in text.split('\n').collect::<Vec<&str>>() {
             let _ = write!(self.stdout, "{line}");
             let _ = self.stdout.queue(Clear(ClearType::UntilNewLine));